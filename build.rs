@@ -126,7 +126,20 @@ fn generate_spaceship_bg() {
     writeln!(f, "];").unwrap();
 }
 
+/// Parse every scene the firmware embeds, so a broken one fails the build
+/// rather than leaving its screen still at runtime.
+fn check_scenes() {
+    for path in ["scenes/bridge.scene"] {
+        println!("cargo:rerun-if-changed={path}");
+        let src = std::fs::read_to_string(path).unwrap();
+        if let Err(e) = ui_core::timeline::Timeline::parse(&src) {
+            panic!("{path}:{}: {}", e.line, e.msg);
+        }
+    }
+}
+
 fn main() {
     embuild::espidf::sysenv::output();
     generate_spaceship_bg();
+    check_scenes();
 }
@@ -1,6 +1,8 @@
 #pragma once
 
 #include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
 #include "esp_err.h"
 
 #ifdef __cplusplus
@@ -8,10 +10,39 @@ extern "C" {
 #endif
 
 /**
- * Initialize the QSPI LCD hardware.
+ * One panel init command; same layout as sh8601_lcd_init_cmd_t.
+ */
+typedef struct {
+    int cmd;
+    const void *data;
+    size_t data_bytes;
+    unsigned int delay_ms;
+} lcd_init_cmd_t;
+
+/**
+ * Panel-specific settings for lcd_driver_init(). Profiles are defined on
+ * the Rust side (src/panel.rs).
+ */
+typedef struct {
+    const char *name;
+    const lcd_init_cmd_t *init_cmds;
+    uint16_t init_cmds_size;
+    int x_gap;
+    int y_gap;
+} lcd_panel_config_t;
+
+/**
+ * Hardware-reset the panel and read its ID (RDID1, 0xDA) over software SPI.
+ * Also brings the display IC out of its power-on state, so it must be
+ * called once before lcd_driver_init().
+ */
+uint8_t lcd_read_id(void);
+
+/**
+ * Initialize the QSPI LCD hardware with the given panel config.
  * Must be called before any drawing operations.
  */
-esp_err_t lcd_driver_init(void);
+esp_err_t lcd_driver_init(const lcd_panel_config_t *panel);
 
 /**
  * Flush pixel data to the display.
@@ -21,7 +52,8 @@ void lcd_draw_bitmap(int x1, int y1, int x2, int y2, const void *data);
 
 /**
  * Start a pixel DMA transfer and return immediately (non-blocking).
- * Call lcd_wait_flush_done() before the next draw or before touching the buffer.
+ * Waits for the previous transfer first, so at most one is in flight.
+ * Call lcd_wait_flush_done() before touching the buffer.
  */
 void lcd_draw_bitmap_async(int x1, int y1, int x2, int y2, const void *data);
 
@@ -31,6 +63,36 @@ void lcd_draw_bitmap_async(int x1, int y1, int x2, int y2, const void *data);
  */
 void lcd_wait_flush_done(void);
 
+/**
+ * Set the panel brightness (DCS 0x51), 0 = off, 255 = max.
+ * The init tables leave it at max.
+ */
+esp_err_t lcd_set_brightness(uint8_t level);
+
+/**
+ * Set the high-brightness-mode level (DCS 0x63) on panels that have one,
+ * such as the CO5300. Not all panels implement this register.
+ */
+esp_err_t lcd_set_hbm_brightness(uint8_t level);
+
+/**
+ * Enable tearing-effect sync on the GPIO wired to the panel's TE output.
+ * The panel pulses TE once per refresh, at the tear scanline set in the
+ * init table (end of frame).
+ */
+esp_err_t lcd_te_enable(int gpio);
+
+/**
+ * Disable TE sync; lcd_wait_te() returns immediately afterwards.
+ */
+void lcd_te_disable(void);
+
+/**
+ * Block until the next TE pulse. Returns false on timeout, true on a pulse
+ * or if TE sync is disabled.
+ */
+bool lcd_wait_te(uint32_t timeout_ms);
+
 #ifdef __cplusplus
 }
 #endif
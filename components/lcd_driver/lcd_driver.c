@@ -29,31 +29,10 @@ static const char *TAG = "lcd_driver";
 #define PIN_LCD_RST    21
 #define PIN_LCD_PWREN  42   // Display power enable (active HIGH)
 
-// ── Init command tables ──────────────────────────────────────────────────────
-// Set to 1 for CO5300 (most 2024+ hardware), 0 for SH8601.
-#define USE_CO5300 1
-
-// SH8601 init sequence
-static const sh8601_lcd_init_cmd_t sh8601_init_cmds[] = {
-    {0x11, NULL,                   0, 120},  // Sleep Out
-    {0x44, (uint8_t[]){0x01,0xD1}, 2,   0},  // Set tear scanline
-    {0x35, (uint8_t[]){0x00},      1,   0},  // TE On
-    {0x53, (uint8_t[]){0x20},      1,  10},  // Write CTRL Display
-    {0x51, (uint8_t[]){0x00},      1,  10},  // Brightness = 0
-    {0x29, NULL,                   0,  10},  // Display On
-    {0x51, (uint8_t[]){0xFF},      1,   0},  // Brightness = max
-};
-
-// CO5300 init sequence (requires x_gap=6)
-static const sh8601_lcd_init_cmd_t co5300_init_cmds[] = {
-    {0x11, NULL,              0,  80},  // Sleep Out
-    {0xC4, (uint8_t[]){0x80}, 1,   0},  // Enable QSPI interface
-    {0x53, (uint8_t[]){0x20}, 1,   1},  // Write CTRL Display
-    {0x63, (uint8_t[]){0xFF}, 1,   1},  // HBM brightness
-    {0x51, (uint8_t[]){0x00}, 1,   1},  // Brightness = 0
-    {0x29, NULL,              0,  10},  // Display On
-    {0x51, (uint8_t[]){0xFF}, 1,   0},  // Brightness = max
-};
+// Panel init tables live on the Rust side (src/panel.rs) and are passed to
+// lcd_driver_init(); lcd_init_cmd_t mirrors sh8601_lcd_init_cmd_t.
+_Static_assert(sizeof(lcd_init_cmd_t) == sizeof(sh8601_lcd_init_cmd_t),
+               "lcd_init_cmd_t must match sh8601_lcd_init_cmd_t");
 
 // ── Software SPI (replicates read_lcd_id_bsp) ───────────────────────────────
 // Both working C examples (07, 09) call read_lcd_id() before power enable.
@@ -133,8 +112,8 @@ static uint8_t spi_read_byte(void)
 // Configures all SPI pins as GPIO, performs hardware reset, then sends a
 // software-SPI read of register 0xDA (RDID1) to detect the controller.
 // CS is held LOW (from lcd_all_gpio_init latch=0) throughout — this matches
-// exactly what the reference examples do.
-static uint8_t soft_spi_read_lcd_id(void)
+// exactly what the reference examples do. Must run before lcd_driver_init().
+uint8_t lcd_read_id(void)
 {
     lcd_all_gpio_init();   // all pins → GPIO output (latch=0 → CS,CLK,D0-3 LOW)
 
@@ -158,9 +137,19 @@ static uint8_t soft_spi_read_lcd_id(void)
 }
 
 // ── State ────────────────────────────────────────────────────────────────────
-static esp_lcd_panel_handle_t s_panel    = NULL;
-static SemaphoreHandle_t      s_flush_sem = NULL;
-static volatile bool          s_dma_idle  = true;  // true = no DMA in flight
+static esp_lcd_panel_handle_t    s_panel     = NULL;
+static esp_lcd_panel_io_handle_t s_io        = NULL;
+static SemaphoreHandle_t         s_flush_sem = NULL;
+static volatile bool             s_dma_idle  = true;  // true = no DMA in flight
+static SemaphoreHandle_t         s_te_sem    = NULL;
+static int                       s_te_gpio   = -1;    // -1 = TE sync off
+
+// QSPI commands go out as opcode 0x02 with the DCS command in bits [15:8]
+// (same encoding esp_lcd_sh8601 uses for its init table).
+#define LCD_OPCODE_WRITE_CMD  0x02
+#define LCD_QSPI_CMD(cmd)     ((LCD_OPCODE_WRITE_CMD << 24) | ((cmd) << 8))
+#define LCD_CMD_WRDISBV       0x51  // Write display brightness
+#define LCD_CMD_WRHBMDISBV    0x63  // Write HBM display brightness
 
 // Called from SPI ISR when the pixel DMA transfer finishes.
 static bool on_color_trans_done(esp_lcd_panel_io_handle_t panel_io,
@@ -172,16 +161,25 @@ static bool on_color_trans_done(esp_lcd_panel_io_handle_t panel_io,
     return high_task_awoken == pdTRUE;
 }
 
+// Called from the GPIO ISR on the rising edge of the panel's TE output.
+static void IRAM_ATTR on_te(void *arg)
+{
+    BaseType_t high_task_awoken = pdFALSE;
+    xSemaphoreGiveFromISR(s_te_sem, &high_task_awoken);
+    if (high_task_awoken) {
+        portYIELD_FROM_ISR();
+    }
+}
+
 // ── Public API ───────────────────────────────────────────────────────────────
 
-esp_err_t lcd_driver_init(void)
+esp_err_t lcd_driver_init(const lcd_panel_config_t *panel)
 {
+    ESP_RETURN_ON_FALSE(panel && panel->init_cmds, ESP_ERR_INVALID_ARG, TAG, "no panel config");
+
     s_flush_sem = xSemaphoreCreateBinary();
     ESP_RETURN_ON_FALSE(s_flush_sem, ESP_ERR_NO_MEM, TAG, "flush semaphore alloc failed");
 
-    // ── Software SPI ID read + hardware reset (matches reference examples) ────
-    uint8_t lcd_id = soft_spi_read_lcd_id();
-
     // ── Display power enable (GPIO 42, active HIGH) ───────────────────────────
     gpio_config_t pwr_cfg = {
         .mode = GPIO_MODE_OUTPUT,
@@ -209,14 +207,11 @@ esp_err_t lcd_driver_init(void)
         esp_lcd_new_panel_io_spi((esp_lcd_spi_bus_handle_t)LCD_HOST, &io_cfg, &io),
         TAG, "panel IO init failed");
 
-    // Panel driver – select init table by detected ID (0x86=SH8601, else CO5300)
-    bool is_sh8601 = (lcd_id == 0x86);
-    ESP_LOGI(TAG, "Using %s init sequence", is_sh8601 ? "SH8601" : "CO5300");
+    // Panel driver – init table chosen by the caller (see src/panel.rs)
+    ESP_LOGI(TAG, "Using %s init sequence", panel->name ? panel->name : "custom");
     sh8601_vendor_config_t vendor_cfg = {
-        .init_cmds      = is_sh8601 ? sh8601_init_cmds : co5300_init_cmds,
-        .init_cmds_size = is_sh8601
-            ? sizeof(sh8601_init_cmds) / sizeof(sh8601_init_cmds[0])
-            : sizeof(co5300_init_cmds) / sizeof(co5300_init_cmds[0]),
+        .init_cmds      = (const sh8601_lcd_init_cmd_t *)panel->init_cmds,
+        .init_cmds_size = panel->init_cmds_size,
         .flags = { .use_qspi_interface = 1 },
     };
 
@@ -228,14 +223,16 @@ esp_err_t lcd_driver_init(void)
     };
     ESP_RETURN_ON_ERROR(esp_lcd_new_panel_sh8601(io, &panel_cfg, &s_panel),
                         TAG, "panel create failed");
+    s_io = io;
 
     ESP_RETURN_ON_ERROR(esp_lcd_panel_reset(s_panel),       TAG, "panel reset failed");
     ESP_RETURN_ON_ERROR(esp_lcd_panel_init(s_panel),        TAG, "panel init failed");
     ESP_RETURN_ON_ERROR(esp_lcd_panel_disp_on_off(s_panel, true), TAG, "display on failed");
 
-    if (!is_sh8601) {
-        // CO5300 has a 6-pixel horizontal offset
-        esp_lcd_panel_set_gap(s_panel, 6, 0);
+    if (panel->x_gap || panel->y_gap) {
+        // e.g. CO5300: 6-pixel horizontal offset
+        ESP_RETURN_ON_ERROR(esp_lcd_panel_set_gap(s_panel, panel->x_gap, panel->y_gap),
+                            TAG, "set gap failed");
     }
 
     ESP_LOGI(TAG, "LCD ready: %d x %d, RGB565", LCD_H_RES, LCD_V_RES);
@@ -247,16 +244,17 @@ void lcd_draw_bitmap(int x1, int y1, int x2, int y2, const void *data)
     // esp_lcd_panel_draw_bitmap enqueues CASET+RASET (sync) then the
     // pixel DMA (async).  We block on s_flush_sem until the ISR callback
     // signals that the DMA is complete.
-    s_dma_idle = false;
-    esp_lcd_panel_draw_bitmap(s_panel, x1, y1, x2, y2, data);
-    xSemaphoreTake(s_flush_sem, portMAX_DELAY);
-    s_dma_idle = true;
+    lcd_draw_bitmap_async(x1, y1, x2, y2, data);
+    lcd_wait_flush_done();
 }
 
 void lcd_draw_bitmap_async(int x1, int y1, int x2, int y2, const void *data)
 {
     // Start pixel DMA and return immediately.  Caller must call
-    // lcd_wait_flush_done() before touching the buffer again.
+    // lcd_wait_flush_done() before touching the buffer again.  The CASET/
+    // RASET that esp_lcd sends first block until the previous colour
+    // transfer is done anyway, so keep just one in flight.
+    lcd_wait_flush_done();
     s_dma_idle = false;
     esp_lcd_panel_draw_bitmap(s_panel, x1, y1, x2, y2, data);
 }
@@ -269,3 +267,63 @@ void lcd_wait_flush_done(void)
         s_dma_idle = true;
     }
 }
+
+esp_err_t lcd_set_brightness(uint8_t level)
+{
+    ESP_RETURN_ON_FALSE(s_io, ESP_ERR_INVALID_STATE, TAG, "LCD not initialised");
+    // tx_param queues behind any pixel DMA still in flight, so this is safe
+    // to call between flushes.
+    return esp_lcd_panel_io_tx_param(s_io, LCD_QSPI_CMD(LCD_CMD_WRDISBV), &level, 1);
+}
+
+esp_err_t lcd_set_hbm_brightness(uint8_t level)
+{
+    ESP_RETURN_ON_FALSE(s_io, ESP_ERR_INVALID_STATE, TAG, "LCD not initialised");
+    return esp_lcd_panel_io_tx_param(s_io, LCD_QSPI_CMD(LCD_CMD_WRHBMDISBV), &level, 1);
+}
+
+esp_err_t lcd_te_enable(int gpio)
+{
+    ESP_RETURN_ON_FALSE(s_te_gpio < 0, ESP_ERR_INVALID_STATE, TAG, "TE already enabled");
+    if (!s_te_sem) {
+        s_te_sem = xSemaphoreCreateBinary();
+        ESP_RETURN_ON_FALSE(s_te_sem, ESP_ERR_NO_MEM, TAG, "TE semaphore alloc failed");
+    }
+
+    gpio_config_t cfg = {
+        .mode         = GPIO_MODE_INPUT,
+        .pull_up_en   = GPIO_PULLUP_DISABLE,
+        .pull_down_en = GPIO_PULLDOWN_ENABLE,
+        .intr_type    = GPIO_INTR_POSEDGE,
+        .pin_bit_mask = BIT_MASK << gpio,
+    };
+    ESP_RETURN_ON_ERROR(gpio_config(&cfg), TAG, "TE GPIO config failed");
+    // The ISR service may already be installed (e.g. by the touch INT pin).
+    esp_err_t ret = gpio_install_isr_service(0);
+    ESP_RETURN_ON_FALSE(ret == ESP_OK || ret == ESP_ERR_INVALID_STATE, ret, TAG, "GPIO ISR service failed");
+    ESP_RETURN_ON_ERROR(gpio_isr_handler_add(gpio, on_te, NULL), TAG, "TE ISR add failed");
+
+    s_te_gpio = gpio;
+    ESP_LOGI(TAG, "TE sync on GPIO %d", gpio);
+    return ESP_OK;
+}
+
+void lcd_te_disable(void)
+{
+    if (s_te_gpio >= 0) {
+        gpio_isr_handler_remove(s_te_gpio);
+        gpio_set_intr_type(s_te_gpio, GPIO_INTR_DISABLE);
+        s_te_gpio = -1;
+    }
+}
+
+bool lcd_wait_te(uint32_t timeout_ms)
+{
+    if (s_te_gpio < 0) {
+        return true;
+    }
+    // Drop a pulse latched while we were busy: it marks a V-blank that has
+    // already (partly) passed.
+    xSemaphoreTake(s_te_sem, 0);
+    return xSemaphoreTake(s_te_sem, pdMS_TO_TICKS(timeout_ms)) == pdTRUE;
+}
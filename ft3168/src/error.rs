@@ -0,0 +1,60 @@
+// ft3168/src/error.rs
+use core::fmt;
+
+use embedded_hal::i2c::{self, ErrorKind};
+
+/// Errors returned by the FT3168 driver. `E` is the underlying bus error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    /// The controller did not acknowledge its address or a data byte.
+    Nack(E),
+    /// The transaction timed out. embedded-hal has no dedicated timeout kind,
+    /// so only errors the check set with `Ft3168::with_timeout_check`
+    /// accepts end up here.
+    Timeout(E),
+    /// Any other bus failure (bus error, arbitration loss, overrun, or an
+    /// unclassified `ErrorKind::Other`).
+    Bus(E),
+    /// TD_STATUS reported more points than the controller can track.
+    ImplausibleCount(u8),
+    /// A point decoded well outside the panel (raw, before clamping).
+    ImplausibleCoordinate { x: u16, y: u16 },
+    /// Re-initialisation after repeated failures did not bring the
+    /// controller back.
+    NotResponding,
+}
+
+impl<E: i2c::Error> Error<E> {
+    /// Classify a bus error by its embedded-hal kind. Timeouts cannot be
+    /// told apart this way; see `Ft3168::with_timeout_check`.
+    pub fn from_bus(e: E) -> Self {
+        match e.kind() {
+            ErrorKind::NoAcknowledge(_) => Error::Nack(e),
+            _ => Error::Bus(e),
+        }
+    }
+}
+
+impl<E> Error<E> {
+    /// `true` for the decoding errors: the bus worked but the report made no sense.
+    pub fn is_implausible(&self) -> bool {
+        matches!(self, Error::ImplausibleCount(_) | Error::ImplausibleCoordinate { .. })
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Nack(e) => write!(f, "FT3168 NACK: {e:?}"),
+            Error::Timeout(e) => write!(f, "FT3168 bus timeout: {e:?}"),
+            Error::Bus(e) => write!(f, "FT3168 bus error: {e:?}"),
+            Error::ImplausibleCount(n) => write!(f, "FT3168 reported {n} touch points"),
+            Error::ImplausibleCoordinate { x, y } => {
+                write!(f, "FT3168 reported touch at ({x}, {y}), outside the panel")
+            }
+            Error::NotResponding => write!(f, "FT3168 not responding after re-init"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for Error<E> {}
@@ -0,0 +1,145 @@
+// ft3168/src/gesture.rs
+//! On-chip gesture engine.
+//!
+//! The FT3168 recognises swipes and double-tap in firmware and latches the
+//! result in GESTURE_ID (0xD3). It has no long-press code, so
+//! [`LongPressDetector`] derives that one from ordinary contact reports.
+use embedded_hal::i2c::I2c;
+
+use crate::{Error, Ft3168};
+
+// Register map (FocalTech FT3x68 gesture application note):
+//   0xD0  GESTURE_EN      1 = gesture engine on
+//   0xD1  GESTURE_ENABLE  per-gesture enable bits (see `GestureSet`)
+//   0xD3  GESTURE_ID      last recognised gesture, 0x00 = none
+const REG_GESTURE_EN: u8 = 0xD0;
+const REG_GESTURE_ENABLE: u8 = 0xD1;
+const REG_GESTURE_ID: u8 = 0xD3;
+
+const ID_SWIPE_LEFT: u8 = 0x20;
+const ID_SWIPE_RIGHT: u8 = 0x21;
+const ID_SWIPE_UP: u8 = 0x22;
+const ID_SWIPE_DOWN: u8 = 0x23;
+const ID_DOUBLE_TAP: u8 = 0x24;
+
+/// A gesture recognised by the controller (or by [`LongPressDetector`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    SwipeUp,
+    SwipeDown,
+    SwipeLeft,
+    SwipeRight,
+    DoubleTap,
+    LongPress,
+}
+
+impl Gesture {
+    /// Decode a GESTURE_ID value. Letter gestures and unknown codes map to `None`.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            ID_SWIPE_LEFT => Some(Gesture::SwipeLeft),
+            ID_SWIPE_RIGHT => Some(Gesture::SwipeRight),
+            ID_SWIPE_UP => Some(Gesture::SwipeUp),
+            ID_SWIPE_DOWN => Some(Gesture::SwipeDown),
+            ID_DOUBLE_TAP => Some(Gesture::DoubleTap),
+            _ => None,
+        }
+    }
+}
+
+/// Bit mask of gestures to enable in GESTURE_ENABLE (0xD1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GestureSet(u8);
+
+impl GestureSet {
+    pub const SWIPE_LEFT: GestureSet = GestureSet(1 << 0);
+    pub const SWIPE_RIGHT: GestureSet = GestureSet(1 << 1);
+    pub const SWIPE_UP: GestureSet = GestureSet(1 << 2);
+    pub const SWIPE_DOWN: GestureSet = GestureSet(1 << 3);
+    pub const DOUBLE_TAP: GestureSet = GestureSet(1 << 4);
+    pub const SWIPES: GestureSet = GestureSet(0x0F);
+    pub const ALL: GestureSet = GestureSet(0x1F);
+
+    pub const fn union(self, other: GestureSet) -> GestureSet {
+        GestureSet(self.0 | other.0)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl<I2C: I2c> Ft3168<I2C> {
+    /// Turn on the gesture engine for the gestures in `set`.
+    /// Double-tap also acts as a wake gesture while the controller is in
+    /// monitor mode.
+    pub fn enable_gestures(&mut self, set: GestureSet) -> Result<(), Error<I2C::Error>> {
+        self.write(&[REG_GESTURE_ENABLE, set.bits()])?;
+        self.write(&[REG_GESTURE_EN, 0x01])?;
+        self.config.gestures = Some(set);
+        Ok(())
+    }
+
+    pub fn disable_gestures(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.write(&[REG_GESTURE_EN, 0x00])?;
+        self.config.gestures = None;
+        Ok(())
+    }
+
+    /// Read the last recognised gesture, if any. GESTURE_ID is latched, so a
+    /// non-zero value is cleared after reading to avoid reporting it twice.
+    pub fn read_gesture(&mut self) -> Result<Option<Gesture>, Error<I2C::Error>> {
+        let mut id = [0u8; 1];
+        self.write_read(REG_GESTURE_ID, &mut id)?;
+        if id[0] != 0 {
+            self.write(&[REG_GESTURE_ID, 0x00])?;
+        }
+        Ok(Gesture::from_id(id[0]))
+    }
+}
+
+/// Synthesises [`Gesture::LongPress`] from contact reports: a finger held
+/// for `hold_ms` without moving more than `slop` pixels on either axis.
+/// Fires at most once per contact; moving past the slop cancels it.
+#[derive(Debug, Clone)]
+pub struct LongPressDetector {
+    hold_ms: u32,
+    slop: u16,
+    // (press time, press x, press y); None while no finger is down.
+    anchor: Option<(u32, u16, u16)>,
+    // Set once this contact has fired or been cancelled by movement.
+    done: bool,
+}
+
+impl LongPressDetector {
+    pub const fn new(hold_ms: u32, slop: u16) -> Self {
+        Self { hold_ms, slop, anchor: None, done: false }
+    }
+
+    /// Feed the current primary touch point (`None` when released) with a
+    /// monotonic timestamp. Returns `true` on the report that completes a
+    /// long press.
+    pub fn update(&mut self, now_ms: u32, point: Option<(u16, u16)>) -> bool {
+        let Some((x, y)) = point else {
+            self.anchor = None;
+            self.done = false;
+            return false;
+        };
+        let Some((t0, x0, y0)) = self.anchor else {
+            self.anchor = Some((now_ms, x, y));
+            return false;
+        };
+        if self.done {
+            return false;
+        }
+        if x.abs_diff(x0) > self.slop || y.abs_diff(y0) > self.slop {
+            self.done = true; // a drag, not a press
+            return false;
+        }
+        if now_ms.wrapping_sub(t0) >= self.hold_ms {
+            self.done = true;
+            return true;
+        }
+        false
+    }
+}
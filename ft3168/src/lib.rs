@@ -0,0 +1,290 @@
+// ft3168/src/lib.rs
+//! Driver for the FocalTech FT3168 capacitive touch controller.
+//!
+//! Generic over any [`embedded_hal::i2c::I2c`] bus, so it runs on the
+//! ESP-IDF `I2cDriver` on the board and on a mock bus in host tests.
+use embedded_hal::i2c::I2c;
+
+mod error;
+mod gesture;
+mod power;
+
+pub use error::Error;
+pub use gesture::{Gesture, GestureSet, LongPressDetector};
+pub use power::{AutoMonitor, PowerMode};
+
+/// 7-bit I2C address of the FT3168.
+pub const ADDR: u8 = 0x38;
+
+/// Largest coordinate on the 466×466 panel; raw values are clamped to it.
+pub const MAX_COORD: u16 = 465;
+
+/// Raw coordinates up to this far past `MAX_COORD` are edge overshoot and get
+/// clamped; anything further out is reported as `Error::ImplausibleCoordinate`.
+pub const COORD_TOLERANCE: u16 = 16;
+
+/// Consecutive failed reads after which `poll` re-initialises the controller.
+pub const DEFAULT_RECOVERY_THRESHOLD: u8 = 5;
+
+/// Let the controller stabilise this long after power-on before `init`, and
+/// after `poll` gives up on it before the next `poll` re-initialises it.
+pub const STARTUP_DELAY: core::time::Duration = core::time::Duration::from_millis(200);
+
+/// Maximum number of simultaneous touch points the FT3168 reports.
+pub const MAX_TOUCH_POINTS: usize = 2;
+
+// Register map (FocalTech FT3x68):
+//   0x02        TD_STATUS  bits[3:0] = number of active points
+//   0x03..0x08  point 1    XH, XL, YH, YL, WEIGHT, MISC
+//   0x09..0x0E  point 2    (same layout)
+//   0xA4        G_MODE     INT pin behaviour (see `InterruptMode`)
+const REG_TD_STATUS: u8 = 0x02;
+const REG_G_MODE: u8 = 0xA4;
+const POINT_REG_LEN: usize = 6;
+
+/// How the controller drives its INT line (G_MODE register).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptMode {
+    /// INT is held low for as long as a touch is active.
+    Polling = 0x00,
+    /// INT pulses low once per new report — use with an edge-triggered GPIO.
+    Trigger = 0x01,
+}
+
+/// Event flag reported with each touch point (XH bits[7:6]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchEvent {
+    PressDown,
+    LiftUp,
+    Contact,
+    NoEvent,
+}
+
+impl TouchEvent {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0x03 {
+            0 => TouchEvent::PressDown,
+            1 => TouchEvent::LiftUp,
+            2 => TouchEvent::Contact,
+            _ => TouchEvent::NoEvent,
+        }
+    }
+}
+
+/// A single touch point as reported by the controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchPoint {
+    /// Touch ID assigned by the controller (YH bits[7:4]). Stable for the
+    /// lifetime of a finger contact, so it can be used to track fingers.
+    pub id: u8,
+    pub event: TouchEvent,
+    pub x: u16,
+    pub y: u16,
+}
+
+impl TouchPoint {
+    const NONE: TouchPoint = TouchPoint {
+        id: 0,
+        event: TouchEvent::NoEvent,
+        x: 0,
+        y: 0,
+    };
+
+    /// Decode one 6-byte point record.
+    // Register layout: buf[0] bits[7:6] = event,   bits[3:0] = X[11:8], buf[1] = X[7:0]
+    //                  buf[2] bits[7:4] = touch ID, bits[3:0] = Y[11:8], buf[3] = Y[7:0]
+    fn decode<E>(buf: &[u8]) -> Result<Self, Error<E>> {
+        let x = (((buf[0] & 0x0F) as u16) << 8) | buf[1] as u16;
+        let y = (((buf[2] & 0x0F) as u16) << 8) | buf[3] as u16;
+        if x > MAX_COORD + COORD_TOLERANCE || y > MAX_COORD + COORD_TOLERANCE {
+            return Err(Error::ImplausibleCoordinate { x, y });
+        }
+        Ok(TouchPoint {
+            id: buf[2] >> 4,
+            event: TouchEvent::from_bits(buf[0] >> 6),
+            x: x.min(MAX_COORD),
+            y: y.min(MAX_COORD),
+        })
+    }
+}
+
+/// All touch points active in one report. Derefs to a slice of the
+/// active points, in the order the controller reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchPoints {
+    points: [TouchPoint; MAX_TOUCH_POINTS],
+    len: usize,
+}
+
+impl TouchPoints {
+    pub const fn empty() -> Self {
+        Self {
+            points: [TouchPoint::NONE; MAX_TOUCH_POINTS],
+            len: 0,
+        }
+    }
+}
+
+impl core::ops::Deref for TouchPoints {
+    type Target = [TouchPoint];
+
+    fn deref(&self) -> &[TouchPoint] {
+        &self.points[..self.len]
+    }
+}
+
+/// Settings written after `init`, replayed when `poll` recovers the controller.
+#[derive(Debug, Clone, Copy, Default)]
+struct Config {
+    interrupt_mode: Option<InterruptMode>,
+    gestures: Option<GestureSet>,
+    auto_monitor: Option<AutoMonitor>,
+}
+
+pub struct Ft3168<I2C: I2c> {
+    i2c: I2C,
+    config: Config,
+    recovery_threshold: u8,
+    failures: u8,
+    reinit_pending: bool,
+    is_timeout: fn(&I2C::Error) -> bool,
+}
+
+impl<I2C: I2c> Ft3168<I2C> {
+    pub fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            config: Config::default(),
+            recovery_threshold: DEFAULT_RECOVERY_THRESHOLD,
+            failures: 0,
+            reinit_pending: false,
+            is_timeout: |_| false,
+        }
+    }
+
+    /// Number of consecutive failed `poll`s before the controller is re-initialised.
+    pub fn with_recovery_threshold(mut self, failures: u8) -> Self {
+        self.recovery_threshold = failures.max(1);
+        self
+    }
+
+    /// Report bus errors `is_timeout` accepts as `Error::Timeout`;
+    /// embedded-hal has no error kind for it.
+    pub fn with_timeout_check(mut self, is_timeout: fn(&I2C::Error) -> bool) -> Self {
+        self.is_timeout = is_timeout;
+        self
+    }
+
+    /// Give the bus back, e.g. to share it with other devices.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Switch FT3168 to normal mode. Call once, `STARTUP_DELAY` after power-on.
+    pub fn init(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.write(&[0x00, 0x00])
+    }
+
+    /// Select how the INT line signals new reports.
+    pub fn set_interrupt_mode(&mut self, mode: InterruptMode) -> Result<(), Error<I2C::Error>> {
+        self.write(&[REG_G_MODE, mode as u8])?;
+        self.config.interrupt_mode = Some(mode);
+        Ok(())
+    }
+
+    /// Returns `Some((x, y))` of the first touch point if a finger is
+    /// currently touching the screen, `None` if no touch is active.
+    pub fn read_touch(&mut self) -> Result<Option<(u16, u16)>, Error<I2C::Error>> {
+        Ok(self.read_touches()?.first().map(|p| (p.x, p.y)))
+    }
+
+    /// Read every active touch point in a single burst starting at TD_STATUS.
+    pub fn read_touches(&mut self) -> Result<TouchPoints, Error<I2C::Error>> {
+        let mut buf = [0u8; 1 + POINT_REG_LEN * MAX_TOUCH_POINTS];
+        self.write_read(REG_TD_STATUS, &mut buf)?;
+
+        let count = buf[0] & 0x0F;
+        if count as usize > MAX_TOUCH_POINTS {
+            return Err(Error::ImplausibleCount(count));
+        }
+        let mut touches = TouchPoints::empty();
+        for (i, rec) in buf[1..].chunks_exact(POINT_REG_LEN).take(count as usize).enumerate() {
+            touches.points[i] = TouchPoint::decode(rec)?;
+        }
+        touches.len = count as usize;
+        Ok(touches)
+    }
+
+    /// `read_touches` with bus recovery: after `recovery_threshold`
+    /// consecutive failures the next call re-initialises the controller and
+    /// replays its configuration first, returning `Error::NotResponding` if
+    /// that fails. Wait `STARTUP_DELAY` before that call while
+    /// `reinit_pending` is set.
+    pub fn poll(&mut self) -> Result<TouchPoints, Error<I2C::Error>> {
+        if self.reinit_pending {
+            self.reinit_pending = false;
+            match self.init().and_then(|()| self.restore()) {
+                Ok(()) => log::info!("FT3168: recovered"),
+                Err(e) => {
+                    log::error!("FT3168: re-init failed: {e}");
+                    return Err(Error::NotResponding);
+                }
+            }
+        }
+        let err = match self.read_touches() {
+            Ok(touches) => {
+                self.failures = 0;
+                return Ok(touches);
+            }
+            Err(e) => e,
+        };
+        self.failures += 1;
+        if self.failures >= self.recovery_threshold {
+            log::warn!(
+                "FT3168: {} consecutive failures (last: {err}), re-initialising",
+                self.failures
+            );
+            self.failures = 0;
+            self.reinit_pending = true;
+        }
+        Err(err)
+    }
+
+    /// `true` once `poll` has given up on the controller, until the next
+    /// `poll` re-initialises it. The delay in between is left to the caller,
+    /// so it can wait without holding whatever the driver is shared through.
+    pub fn reinit_pending(&self) -> bool {
+        self.reinit_pending
+    }
+
+    /// Re-apply every setting recorded in `config`.
+    fn restore(&mut self) -> Result<(), Error<I2C::Error>> {
+        let Config { interrupt_mode, gestures, auto_monitor } = self.config;
+        if let Some(mode) = interrupt_mode {
+            self.set_interrupt_mode(mode)?;
+        }
+        if let Some(set) = gestures {
+            self.enable_gestures(set)?;
+        }
+        if auto_monitor.is_some() {
+            self.set_auto_monitor(auto_monitor)?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Error<I2C::Error>> {
+        self.i2c.write(ADDR, bytes).map_err(|e| self.bus_error(e))
+    }
+
+    fn write_read(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Error<I2C::Error>> {
+        self.i2c.write_read(ADDR, &[reg], buf).map_err(|e| self.bus_error(e))
+    }
+
+    fn bus_error(&self, e: I2C::Error) -> Error<I2C::Error> {
+        if (self.is_timeout)(&e) {
+            Error::Timeout(e)
+        } else {
+            Error::from_bus(e)
+        }
+    }
+}
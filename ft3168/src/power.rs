@@ -0,0 +1,72 @@
+// ft3168/src/power.rs
+//! Power-mode control.
+//!
+//! The controller has three modes: active (full report rate), monitor (slow
+//! periodic scan, drops back to active on touch) and hibernate (no scanning
+//! at all). It can also enter monitor mode on its own after an idle period.
+use embedded_hal::i2c::I2c;
+
+use crate::{Error, Ft3168};
+
+// Register map (FocalTech FT3x68):
+//   0x86  G_CTRL               0 = stay active, 1 = auto-switch to monitor
+//   0x87  G_TIMEENTERMONITOR   idle seconds before auto-switching
+//   0x89  G_PERIODMONITOR      scan period in monitor mode
+//   0xA5  G_PMODE              current power mode (see `PowerMode`)
+const REG_G_CTRL: u8 = 0x86;
+const REG_G_TIMEENTERMONITOR: u8 = 0x87;
+const REG_G_PERIODMONITOR: u8 = 0x89;
+const REG_G_PMODE: u8 = 0xA5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerMode {
+    /// Full-rate scanning.
+    Active = 0x00,
+    /// Slow periodic scan; a touch raises INT and returns to active.
+    Monitor = 0x01,
+    /// Scanning stopped. Only a reset on TP_RST (or a power cycle) wakes the
+    /// controller — on boards with TP_RST tied high this lasts until reboot.
+    Hibernate = 0x03,
+}
+
+/// Automatic active → monitor switching after an idle period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoMonitor {
+    /// Seconds without a touch before the controller drops to monitor mode.
+    pub idle_secs: u8,
+    /// Monitor-mode scan period, in controller units (datasheet default 0x28).
+    pub scan_period: u8,
+}
+
+impl<I2C: I2c> Ft3168<I2C> {
+    pub fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), Error<I2C::Error>> {
+        self.write(&[REG_G_PMODE, mode as u8])
+    }
+
+    /// Read back the current power mode. Codes outside the documented set
+    /// are reported as `None`.
+    pub fn power_mode(&mut self) -> Result<Option<PowerMode>, Error<I2C::Error>> {
+        let mut mode = [0u8; 1];
+        self.write_read(REG_G_PMODE, &mut mode)?;
+        Ok(match mode[0] {
+            0x00 => Some(PowerMode::Active),
+            0x01 => Some(PowerMode::Monitor),
+            0x03 => Some(PowerMode::Hibernate),
+            _ => None,
+        })
+    }
+
+    /// Enable (`Some`) or disable (`None`) automatic switching to monitor mode.
+    pub fn set_auto_monitor(&mut self, auto: Option<AutoMonitor>) -> Result<(), Error<I2C::Error>> {
+        match auto {
+            Some(cfg) => {
+                self.write(&[REG_G_TIMEENTERMONITOR, cfg.idle_secs])?;
+                self.write(&[REG_G_PERIODMONITOR, cfg.scan_period])?;
+                self.write(&[REG_G_CTRL, 0x01])?;
+            }
+            None => self.write(&[REG_G_CTRL, 0x00])?,
+        }
+        self.config.auto_monitor = auto;
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+// ft3168/tests/driver.rs
+mod mock;
+
+use ft3168::{Error, Ft3168, InterruptMode, TouchEvent, TouchPoint, ADDR, MAX_COORD};
+use embedded_hal::i2c::ErrorKind;
+use mock::{MockBus, MockError};
+
+const REG_TD_STATUS: u8 = 0x02;
+
+/// Build a full TD_STATUS burst: count byte followed by two 6-byte records.
+fn report(count: u8, p1: [u8; 4], p2: [u8; 4]) -> [u8; 13] {
+    let mut buf = [0u8; 13];
+    buf[0] = count;
+    buf[1..5].copy_from_slice(&p1);
+    buf[7..11].copy_from_slice(&p2);
+    buf
+}
+
+#[test]
+fn init_enters_normal_mode() {
+    let bus = MockBus::new().write(ADDR, &[0x00, 0x00]);
+    let mut touch = Ft3168::new(bus);
+    touch.init().unwrap();
+    touch.release().done();
+}
+
+#[test]
+fn sets_interrupt_mode() {
+    let bus = MockBus::new()
+        .write(ADDR, &[0xA4, 0x01])
+        .write(ADDR, &[0xA4, 0x00]);
+    let mut touch = Ft3168::new(bus);
+    touch.set_interrupt_mode(InterruptMode::Trigger).unwrap();
+    touch.set_interrupt_mode(InterruptMode::Polling).unwrap();
+    touch.release().done();
+}
+
+#[test]
+fn count_zero_reports_no_touch() {
+    let bus = MockBus::new()
+        .write_read(ADDR, &[REG_TD_STATUS], &report(0, [0x81, 0x23, 0x12, 0x34], [0; 4]))
+        .write_read(ADDR, &[REG_TD_STATUS], &report(0, [0; 4], [0; 4]));
+    let mut touch = Ft3168::new(bus);
+    assert!(touch.read_touches().unwrap().is_empty());
+    assert_eq!(touch.read_touch().unwrap(), None);
+    touch.release().done();
+}
+
+#[test]
+fn decodes_single_point_registers() {
+    // XH: event=Contact (0b10), X[11:8]=0x1; YH: ID=3, Y[11:8]=0x0
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(1, [0x81, 0x23, 0x30, 0x45], [0; 4]),
+    );
+    let mut touch = Ft3168::new(bus);
+    let points = touch.read_touches().unwrap();
+    assert_eq!(
+        *points,
+        [TouchPoint { id: 3, event: TouchEvent::Contact, x: 0x123, y: 0x045 }]
+    );
+    touch.release().done();
+}
+
+#[test]
+fn decodes_both_points_and_event_flags() {
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(2, [0x00, 0x10, 0x00, 0x20], [0x40, 0x30, 0x10, 0x40]),
+    );
+    let mut touch = Ft3168::new(bus);
+    let points = touch.read_touches().unwrap();
+    assert_eq!(points.len(), 2);
+    assert_eq!((points[0].id, points[0].event), (0, TouchEvent::PressDown));
+    assert_eq!((points[1].id, points[1].event), (1, TouchEvent::LiftUp));
+    assert_eq!((points[1].x, points[1].y), (0x30, 0x40));
+    touch.release().done();
+}
+
+#[test]
+fn read_touch_returns_first_point() {
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(2, [0x80, 0x64, 0x00, 0xC8], [0x80, 0x0A, 0x10, 0x14]),
+    );
+    let mut touch = Ft3168::new(bus);
+    assert_eq!(touch.read_touch().unwrap(), Some((100, 200)));
+    touch.release().done();
+}
+
+#[test]
+fn masks_coordinates_to_12_bits() {
+    // Event and ID bits in the high nibbles must not leak into X/Y.
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(1, [0xC1, 0x00, 0xF1, 0x00], [0; 4]),
+    );
+    let mut touch = Ft3168::new(bus);
+    let p = touch.read_touches().unwrap()[0];
+    assert_eq!((p.x, p.y), (0x100, 0x100));
+    assert_eq!((p.id, p.event), (0xF, TouchEvent::NoEvent));
+    touch.release().done();
+}
+
+#[test]
+fn clamps_coordinates_to_panel() {
+    // 0x1E1 = 481 is the last raw value still treated as edge overshoot.
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(1, [0x81, 0xE1, 0x01, 0xD2], [0; 4]),
+    );
+    let mut touch = Ft3168::new(bus);
+    let p = touch.read_touches().unwrap()[0];
+    assert_eq!(p.x, MAX_COORD);
+    assert_eq!(p.y, 465); // 0x1D2 = 466
+    touch.release().done();
+}
+
+#[test]
+fn far_out_of_range_coordinates_are_implausible() {
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(1, [0x8F, 0xFF, 0x00, 0x10], [0; 4]),
+    );
+    let mut touch = Ft3168::new(bus);
+    assert_eq!(
+        touch.read_touches(),
+        Err(Error::ImplausibleCoordinate { x: 0xFFF, y: 0x10 })
+    );
+    touch.release().done();
+}
+
+#[test]
+fn count_above_capacity_is_implausible() {
+    let bus = MockBus::new().write_read(
+        ADDR,
+        &[REG_TD_STATUS],
+        &report(0x05, [0x80, 0x01, 0x00, 0x02], [0x80, 0x03, 0x10, 0x04]),
+    );
+    let mut touch = Ft3168::new(bus);
+    let err = touch.read_touches().unwrap_err();
+    assert_eq!(err, Error::ImplausibleCount(5));
+    assert!(err.is_implausible());
+    touch.release().done();
+}
+
+#[test]
+fn bus_errors_are_classified() {
+    let bus = MockBus::new()
+        .write_read(ADDR, &[REG_TD_STATUS], &[0; 13])
+        .fail(MockError::nack())
+        .write_read(ADDR, &[REG_TD_STATUS], &[0; 13])
+        .fail(MockError(ErrorKind::Other))
+        .write_read(ADDR, &[REG_TD_STATUS], &[0; 13])
+        .fail(MockError(ErrorKind::ArbitrationLoss));
+    let mut touch = Ft3168::new(bus);
+    assert_eq!(touch.read_touches(), Err(Error::Nack(MockError::nack())));
+    // Without a timeout check, `Other` is just a bus error.
+    assert_eq!(
+        touch.read_touches(),
+        Err(Error::Bus(MockError(ErrorKind::Other)))
+    );
+    assert_eq!(
+        touch.read_touches(),
+        Err(Error::Bus(MockError(ErrorKind::ArbitrationLoss)))
+    );
+    touch.release().done();
+}
+
+#[test]
+fn timeout_check_classifies_timeouts() {
+    let bus = MockBus::new()
+        .write_read(ADDR, &[REG_TD_STATUS], &[0; 13])
+        .fail(MockError(ErrorKind::Other))
+        .write_read(ADDR, &[REG_TD_STATUS], &[0; 13])
+        .fail(MockError(ErrorKind::Bus));
+    let mut touch =
+        Ft3168::new(bus).with_timeout_check(|e| *e == MockError(ErrorKind::Other));
+    assert_eq!(
+        touch.read_touches(),
+        Err(Error::Timeout(MockError(ErrorKind::Other)))
+    );
+    assert_eq!(touch.read_touches(), Err(Error::Bus(MockError(ErrorKind::Bus))));
+    touch.release().done();
+}
@@ -0,0 +1,68 @@
+// ft3168/tests/gesture.rs
+mod mock;
+
+use ft3168::{Ft3168, Gesture, GestureSet, LongPressDetector, ADDR};
+use mock::MockBus;
+
+#[test]
+fn enable_writes_mask_then_engine_on() {
+    let bus = MockBus::new()
+        .write(ADDR, &[0xD1, 0x1F])
+        .write(ADDR, &[0xD0, 0x01])
+        .write(ADDR, &[0xD1, 0x11])
+        .write(ADDR, &[0xD0, 0x01])
+        .write(ADDR, &[0xD0, 0x00]);
+    let mut touch = Ft3168::new(bus);
+    touch.enable_gestures(GestureSet::ALL).unwrap();
+    touch
+        .enable_gestures(GestureSet::SWIPE_LEFT.union(GestureSet::DOUBLE_TAP))
+        .unwrap();
+    touch.disable_gestures().unwrap();
+    touch.release().done();
+}
+
+#[test]
+fn decodes_gesture_ids() {
+    let cases = [
+        (0x20, Some(Gesture::SwipeLeft)),
+        (0x21, Some(Gesture::SwipeRight)),
+        (0x22, Some(Gesture::SwipeUp)),
+        (0x23, Some(Gesture::SwipeDown)),
+        (0x24, Some(Gesture::DoubleTap)),
+        (0x00, None),
+        (0x30, None), // letter 'O' — not exposed
+    ];
+    let mut bus = MockBus::new();
+    for (id, _) in cases {
+        bus = bus.write_read(ADDR, &[0xD3], &[id]);
+        if id != 0 {
+            bus = bus.write(ADDR, &[0xD3, 0x00]);
+        }
+    }
+    let mut touch = Ft3168::new(bus);
+    for (_, expected) in cases {
+        assert_eq!(touch.read_gesture().unwrap(), expected);
+    }
+    touch.release().done();
+}
+
+#[test]
+fn long_press_fires_once_after_hold() {
+    let mut lp = LongPressDetector::new(600, 10);
+    assert!(!lp.update(0, Some((100, 100))));
+    assert!(!lp.update(300, Some((104, 98))));
+    assert!(lp.update(600, Some((105, 101))));
+    assert!(!lp.update(900, Some((105, 101))));
+    // Release re-arms it.
+    assert!(!lp.update(950, None));
+    assert!(!lp.update(1000, Some((50, 50))));
+    assert!(lp.update(1600, Some((50, 50))));
+}
+
+#[test]
+fn long_press_cancelled_by_drag() {
+    let mut lp = LongPressDetector::new(600, 10);
+    assert!(!lp.update(0, Some((100, 100))));
+    assert!(!lp.update(100, Some((130, 100))));
+    assert!(!lp.update(800, Some((130, 100))));
+}
@@ -0,0 +1,92 @@
+// ft3168/tests/mock/mod.rs
+// Scripted I2C bus for host tests: each call must match the next expected
+// transaction, and returns the scripted response bytes or error.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use embedded_hal::i2c::{self, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockError(pub ErrorKind);
+
+impl i2c::Error for MockError {
+    fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+impl MockError {
+    pub fn nack() -> Self {
+        MockError(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+    }
+}
+
+#[derive(Debug)]
+enum Expect {
+    Write { addr: u8, bytes: Vec<u8> },
+    WriteRead { addr: u8, write: Vec<u8>, read: Vec<u8> },
+}
+
+#[derive(Default)]
+pub struct MockBus {
+    script: VecDeque<(Expect, Result<(), MockError>)>,
+}
+
+impl MockBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a plain write of `bytes` to `addr`.
+    pub fn write(mut self, addr: u8, bytes: &[u8]) -> Self {
+        let bytes = bytes.to_vec();
+        self.script.push_back((Expect::Write { addr, bytes }, Ok(())));
+        self
+    }
+
+    /// Expect a register read: write `reg`, then answer with `read`.
+    pub fn write_read(mut self, addr: u8, reg: &[u8], read: &[u8]) -> Self {
+        let expect = Expect::WriteRead { addr, write: reg.to_vec(), read: read.to_vec() };
+        self.script.push_back((expect, Ok(())));
+        self
+    }
+
+    /// Make the most recently scripted transaction fail with `err`.
+    pub fn fail(mut self, err: MockError) -> Self {
+        self.script.back_mut().expect("nothing scripted to fail").1 = Err(err);
+        self
+    }
+
+    /// Panics if any scripted transaction was not performed.
+    pub fn done(&self) {
+        assert!(self.script.is_empty(), "unconsumed transactions: {:?}", self.script);
+    }
+}
+
+impl ErrorType for MockBus {
+    type Error = MockError;
+}
+
+impl I2c for MockBus {
+    fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), MockError> {
+        let (expect, result) = self
+            .script
+            .pop_front()
+            .unwrap_or_else(|| panic!("unexpected transaction to {address:#04x}"));
+        match (expect, operations) {
+            (Expect::Write { addr, bytes }, [Operation::Write(w)]) => {
+                assert_eq!(addr, address);
+                assert_eq!(bytes.as_slice(), *w);
+            }
+            (Expect::WriteRead { addr, write, read }, [Operation::Write(w), Operation::Read(r)]) => {
+                assert_eq!(addr, address);
+                assert_eq!(write.as_slice(), *w);
+                assert_eq!(read.len(), r.len(), "read length mismatch");
+                r.copy_from_slice(&read);
+            }
+            (expect, ops) => panic!("expected {expect:?}, got {} operation(s)", ops.len()),
+        }
+        result
+    }
+}
@@ -0,0 +1,44 @@
+// ft3168/tests/power.rs
+mod mock;
+
+use ft3168::{AutoMonitor, Ft3168, PowerMode, ADDR};
+use mock::MockBus;
+
+#[test]
+fn writes_power_mode_codes() {
+    let bus = MockBus::new()
+        .write(ADDR, &[0xA5, 0x01])
+        .write(ADDR, &[0xA5, 0x03])
+        .write(ADDR, &[0xA5, 0x00]);
+    let mut touch = Ft3168::new(bus);
+    touch.set_power_mode(PowerMode::Monitor).unwrap();
+    touch.set_power_mode(PowerMode::Hibernate).unwrap();
+    touch.set_power_mode(PowerMode::Active).unwrap();
+    touch.release().done();
+}
+
+#[test]
+fn reads_power_mode() {
+    let bus = MockBus::new()
+        .write_read(ADDR, &[0xA5], &[0x01])
+        .write_read(ADDR, &[0xA5], &[0x07]);
+    let mut touch = Ft3168::new(bus);
+    assert_eq!(touch.power_mode().unwrap(), Some(PowerMode::Monitor));
+    assert_eq!(touch.power_mode().unwrap(), None);
+    touch.release().done();
+}
+
+#[test]
+fn auto_monitor_configures_then_enables() {
+    let bus = MockBus::new()
+        .write(ADDR, &[0x87, 5])
+        .write(ADDR, &[0x89, 0x28])
+        .write(ADDR, &[0x86, 0x01])
+        .write(ADDR, &[0x86, 0x00]);
+    let mut touch = Ft3168::new(bus);
+    touch
+        .set_auto_monitor(Some(AutoMonitor { idle_secs: 5, scan_period: 0x28 }))
+        .unwrap();
+    touch.set_auto_monitor(None).unwrap();
+    touch.release().done();
+}
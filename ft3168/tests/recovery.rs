@@ -0,0 +1,75 @@
+// ft3168/tests/recovery.rs
+mod mock;
+
+use ft3168::{Error, Ft3168, GestureSet, InterruptMode, ADDR};
+use mock::{MockBus, MockError};
+
+const REG_TD_STATUS: u8 = 0x02;
+
+fn failed_read(bus: MockBus) -> MockBus {
+    bus.write_read(ADDR, &[REG_TD_STATUS], &[0; 13]).fail(MockError::nack())
+}
+
+#[test]
+fn success_resets_failure_count() {
+    let mut bus = MockBus::new();
+    bus = failed_read(bus);
+    bus = failed_read(bus);
+    bus = bus.write_read(ADDR, &[REG_TD_STATUS], &[0; 13]);
+    bus = failed_read(bus);
+    bus = failed_read(bus);
+    let mut touch = Ft3168::new(bus).with_recovery_threshold(3);
+    for _ in 0..2 {
+        assert_eq!(touch.poll(), Err(Error::Nack(MockError::nack())));
+    }
+    assert!(touch.poll().unwrap().is_empty());
+    // Two more failures: still below the threshold, so no re-init traffic.
+    for _ in 0..2 {
+        assert!(touch.poll().is_err());
+    }
+    touch.release().done();
+}
+
+#[test]
+fn reinitialises_and_replays_config_after_threshold() {
+    let mut bus = MockBus::new()
+        .write(ADDR, &[0xA4, 0x01])
+        .write(ADDR, &[0xD1, 0x0F])
+        .write(ADDR, &[0xD0, 0x01]);
+    for _ in 0..3 {
+        bus = failed_read(bus);
+    }
+    bus = bus
+        .write(ADDR, &[0x00, 0x00])
+        .write(ADDR, &[0xA4, 0x01])
+        .write(ADDR, &[0xD1, 0x0F])
+        .write(ADDR, &[0xD0, 0x01])
+        .write_read(ADDR, &[REG_TD_STATUS], &[0; 13]);
+
+    let mut touch = Ft3168::new(bus).with_recovery_threshold(3);
+    touch.set_interrupt_mode(InterruptMode::Trigger).unwrap();
+    touch.enable_gestures(GestureSet::SWIPES).unwrap();
+    for _ in 0..3 {
+        assert!(!touch.reinit_pending());
+        assert!(touch.poll().is_err());
+    }
+    // The re-init waits for the next poll, so the caller can sleep first.
+    assert!(touch.reinit_pending());
+    assert!(touch.poll().unwrap().is_empty());
+    assert!(!touch.reinit_pending());
+    touch.release().done();
+}
+
+#[test]
+fn failed_reinit_reports_not_responding() {
+    let mut bus = MockBus::new();
+    for _ in 0..2 {
+        bus = failed_read(bus);
+    }
+    bus = bus.write(ADDR, &[0x00, 0x00]).fail(MockError::nack());
+    let mut touch = Ft3168::new(bus).with_recovery_threshold(2);
+    assert!(touch.poll().is_err());
+    assert_eq!(touch.poll(), Err(Error::Nack(MockError::nack())));
+    assert_eq!(touch.poll(), Err(Error::NotResponding));
+    touch.release().done();
+}
@@ -0,0 +1,593 @@
+// src/anim.rs
+// Frame-based sprite animation. A `FrameSet` holds the image descriptors for
+// a run of frames and can be shared; an `AnimatedSprite` is one on-screen
+// instance with its own frame durations, phase, jitter and LVGL image
+// widget, and can switch `Clip`s, tween its position, opacity and zoom,
+// walk a `Path` or show a speech bubble. A `Scheduler` steps every sprite it
+// holds, and an optional director driving them, from a single LVGL timer,
+// and reports taps on the opaque pixels of tappable sprites. LVGL thread
+// only.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::rc::Rc;
+
+pub use ui_core::motion::{Easing, LoopMode, Path, Prop, Timing};
+use ui_core::motion::Playback;
+
+use crate::{safe_area, touch};
+
+/// Scheduler tick: frame changes happen up to this late, but late time is
+/// carried over, so the average frame rate stays exact.
+const TICK_MS: u32 = 20;
+
+/// A tap this close to an opaque pixel of a tappable sprite hits it.
+const HIT_SLOP: i32 = 6;
+/// A press that moved further than this before release is not a tap.
+const TAP_SLOP: i32 = 12;
+/// Speech bubbles: widest before wrapping, gap to the sprite.
+const BUBBLE_MAX_W: i32 = 180;
+const BUBBLE_GAP: i32 = 4;
+
+/// The LVGL callback for `easing`'s curve.
+pub(crate) fn path_cb(easing: Easing) -> lvgl_sys::lv_anim_path_cb_t {
+    Some(match easing {
+        Easing::Linear => lvgl_sys::lv_anim_path_linear,
+        Easing::EaseIn => lvgl_sys::lv_anim_path_ease_in,
+        Easing::EaseOut => lvgl_sys::lv_anim_path_ease_out,
+        Easing::EaseInOut => lvgl_sys::lv_anim_path_ease_in_out,
+        Easing::Overshoot => lvgl_sys::lv_anim_path_overshoot,
+    })
+}
+
+/// Image descriptors for a run of same-sized RGB565 frames. Share one
+/// between sprites with `Rc`.
+pub struct FrameSet {
+    // Boxed slice: LVGL keeps pointers to these while any sprite uses them.
+    dscs: Box<[lvgl_sys::lv_img_dsc_t]>,
+    w: u32,
+    h: u32,
+}
+
+impl FrameSet {
+    /// `frames` are `w`×`h` pixels each.
+    pub fn new(w: u32, h: u32, frames: &[&'static [u16]]) -> Rc<Self> {
+        assert!(!frames.is_empty(), "frame set needs at least one frame");
+        Rc::new(Self { dscs: frames.iter().map(|px| image_dsc(px, w, h)).collect(), w, h })
+    }
+
+    pub fn len(&self) -> usize {
+        self.dscs.len()
+    }
+
+    fn src(&self, frame: usize) -> *const core::ffi::c_void {
+        &self.dscs[frame] as *const _ as *const _
+    }
+}
+
+impl Drop for FrameSet {
+    fn drop(&mut self) {
+        // The image cache is keyed by descriptor address; a later
+        // allocation at the same address must not hit a stale entry.
+        for dsc in self.dscs.iter() {
+            unsafe { lvgl_sys::lv_img_cache_invalidate_src(dsc as *const _ as *const _) };
+        }
+    }
+}
+
+/// Frames to show and how: what a sprite plays.
+#[derive(Clone)]
+pub struct Clip {
+    frames: Rc<FrameSet>,
+    durations: Vec<u32>,
+    mode: LoopMode,
+}
+
+impl Clip {
+    /// `frame_ms` holds one duration per frame, or a single one for all of
+    /// them.
+    pub fn new(frames: &Rc<FrameSet>, frame_ms: &[u32], mode: LoopMode) -> Self {
+        let durations = match frame_ms {
+            [ms] => vec![(*ms).max(1); frames.len()],
+            _ => {
+                assert_eq!(frame_ms.len(), frames.len(), "one duration per frame");
+                frame_ms.iter().map(|ms| (*ms).max(1)).collect()
+            }
+        };
+        Self { frames: frames.clone(), durations, mode }
+    }
+}
+
+// ── Tweens and paths ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct Tween {
+    prop: Prop,
+    from: i32,
+    to: i32,
+    duration_ms: u32,
+    elapsed_ms: u32,
+    easing: Easing,
+}
+
+impl Tween {
+    fn value(&self) -> i32 {
+        let t = (self.elapsed_ms.min(self.duration_ms) * 1000 / self.duration_ms) as i32;
+        self.from + (self.to - self.from) * self.easing.apply(t) / 1000
+    }
+
+    fn is_done(&self) -> bool {
+        self.elapsed_ms >= self.duration_ms
+    }
+}
+
+/// A speech bubble next to a sprite. Dropping it deletes the label.
+struct Bubble {
+    label: *mut lvgl_sys::lv_obj_t,
+    left_ms: u32,
+}
+
+impl Drop for Bubble {
+    fn drop(&mut self) {
+        unsafe { lvgl_sys::lv_obj_del(self.label) };
+    }
+}
+
+/// A path being walked.
+struct Walk {
+    points: VecDeque<(i32, i32)>,
+    speed_px_s: u32,
+    easing: Easing,
+}
+
+// ── Sprites ─────────────────────────────────────────────────────────────────
+
+/// An image widget cycling through a `FrameSet`. Dropping it deletes the
+/// widget, so drop it before its parent is deleted, or not at all.
+pub struct AnimatedSprite {
+    frames: Rc<FrameSet>,
+    playback: Playback,
+    widget: *mut lvgl_sys::lv_obj_t,
+    // Current values of the tweenable properties.
+    x: i32,
+    y: i32,
+    opa: i32,
+    zoom: i32,
+    /// At most one per property.
+    tweens: Vec<Tween>,
+    walk: Option<Walk>,
+    bubble: Option<Bubble>,
+}
+
+impl AnimatedSprite {
+    /// A sprite under `parent` playing `clip`, starting on its first frame.
+    pub fn new(parent: *mut lvgl_sys::lv_obj_t, clip: &Clip) -> Self {
+        let widget = unsafe {
+            let widget = lvgl_sys::lv_img_create(parent);
+            lvgl_sys::lv_img_set_src(widget, clip.frames.src(0));
+            widget
+        };
+        Self {
+            frames: clip.frames.clone(),
+            playback: Playback::new(clip.durations.clone(), clip.mode),
+            widget,
+            x: 0,
+            y: 0,
+            opa: lvgl_sys::LV_OPA_COVER as i32,
+            zoom: lvgl_sys::LV_IMG_ZOOM_NONE as i32,
+            tweens: Vec::new(),
+            walk: None,
+            bubble: None,
+        }
+    }
+
+    /// Apply per-instance phase and jitter. Restarts the animation.
+    pub fn with_timing(mut self, timing: Timing) -> Self {
+        // SAFETY: reads the hardware RNG; no preconditions.
+        let seed = unsafe { esp_idf_svc::sys::esp_random() };
+        self.playback.set_timing(timing, seed);
+        let frame = self.playback.frame();
+        self.show(frame);
+        self
+    }
+
+    /// Switch to `clip` from its first frame. Jitter carries over; phase
+    /// does not.
+    pub fn play(&mut self, clip: &Clip) {
+        self.frames = clip.frames.clone();
+        self.playback.replace(clip.durations.clone(), clip.mode);
+        self.show(0);
+    }
+
+    /// Move the top-left corner to (`x`, `y`) in the parent. Stops any
+    /// movement.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.stop_moving();
+        self.set(Prop::X, x);
+        self.set(Prop::Y, y);
+    }
+
+    /// Animate `prop` from its current value to `to` over `duration_ms`,
+    /// replacing any tween of it already running. Tweening `X` or `Y` stops
+    /// a path being walked.
+    pub fn tween(&mut self, prop: Prop, to: i32, duration_ms: u32, easing: Easing) {
+        if matches!(prop, Prop::X | Prop::Y) {
+            self.walk = None;
+        }
+        self.start_tween(prop, to, duration_ms, easing);
+    }
+
+    /// Walk `path` from wherever the sprite is now. Waypoints that would put
+    /// part of the sprite off the round display are pulled in with
+    /// `clamp_rect_to_display`. Checking the waypoints is enough: the
+    /// display is convex, so a straight leg between two rectangles that fit
+    /// stays on it.
+    pub fn follow(&mut self, path: &Path) {
+        let (w, h) = (self.frames.w as i32, self.frames.h as i32);
+        let points = path
+            .points()
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| {
+                if safe_area::rect_in_display(x, y, w, h) {
+                    return (x, y);
+                }
+                let clamped = safe_area::clamp_rect_to_display(x, y, w, h);
+                log::warn!("path waypoint {i} ({x}, {y}) is off the display, using {clamped:?}");
+                clamped
+            })
+            .collect();
+        self.stop_moving();
+        self.walk = Some(Walk { points, speed_px_s: path.speed_px_s(), easing: path.leg_easing() });
+        self.next_leg();
+    }
+
+    /// Show `text` in a bubble above the sprite (below, if there is no room)
+    /// for `ms`, replacing any bubble it has. The bubble follows the sprite.
+    pub fn say(&mut self, text: &str, ms: u32) {
+        self.bubble = None;
+        let text = CString::new(text).unwrap_or_default();
+        let label = unsafe {
+            let label = lvgl_sys::lv_label_create(lvgl_sys::lv_obj_get_parent(self.widget));
+            // lv_label_set_text copies the string.
+            lvgl_sys::lv_label_set_text(label, text.as_ptr());
+            lvgl_sys::lv_obj_set_style_max_width(
+                label,
+                BUBBLE_MAX_W as lvgl_sys::lv_coord_t,
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_set_style_bg_color(
+                label,
+                lvgl_sys::_LV_COLOR_MAKE(0xFF, 0xFF, 0xFF),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_set_style_bg_opa(label, lvgl_sys::LV_OPA_COVER as u8, lvgl_sys::LV_STATE_DEFAULT);
+            lvgl_sys::lv_obj_set_style_text_color(
+                label,
+                lvgl_sys::_LV_COLOR_MAKE(0x1a, 0x20, 0x40),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_set_style_radius(label, 8, lvgl_sys::LV_STATE_DEFAULT);
+            lvgl_sys::lv_obj_set_style_pad_all(label, 6, lvgl_sys::LV_STATE_DEFAULT);
+            label
+        };
+        self.bubble = Some(Bubble { label, left_ms: ms });
+        self.place_bubble();
+    }
+
+    fn advance(&mut self, dt_ms: u32) {
+        if let Some(frame) = self.playback.advance(dt_ms) {
+            self.show(frame);
+        }
+        if let Some(bubble) = &mut self.bubble {
+            bubble.left_ms = bubble.left_ms.saturating_sub(dt_ms);
+            if bubble.left_ms == 0 {
+                self.bubble = None;
+            }
+        }
+        if self.tweens.is_empty() && self.walk.is_none() {
+            return;
+        }
+        for tween in &mut self.tweens {
+            tween.elapsed_ms += dt_ms;
+        }
+        let values: Vec<_> = self.tweens.iter().map(|t| (t.prop, t.value())).collect();
+        for (prop, value) in values {
+            self.set(prop, value);
+        }
+        self.tweens.retain(|t| !t.is_done());
+        if !self.tweens.iter().any(|t| matches!(t.prop, Prop::X | Prop::Y)) {
+            self.next_leg();
+        }
+    }
+
+    fn place_bubble(&self) {
+        let Some(bubble) = &self.bubble else {
+            return;
+        };
+        unsafe {
+            lvgl_sys::lv_obj_update_layout(bubble.label);
+            let w = lvgl_sys::lv_obj_get_width(bubble.label) as i32;
+            let h = lvgl_sys::lv_obj_get_height(bubble.label) as i32;
+            let x = self.x + self.frames.w as i32 / 2 - w / 2;
+            let mut y = self.y - BUBBLE_GAP - h;
+            if !safe_area::rect_in_display(x, y, w, h) {
+                y = self.y + self.frames.h as i32 + BUBBLE_GAP;
+            }
+            let (x, y) = safe_area::clamp_rect_to_display(x, y, w, h);
+            lvgl_sys::lv_obj_set_pos(bubble.label, x as lvgl_sys::lv_coord_t, y as lvgl_sys::lv_coord_t);
+        }
+    }
+
+    fn stop_moving(&mut self) {
+        self.walk = None;
+        self.tweens.retain(|t| !matches!(t.prop, Prop::X | Prop::Y));
+    }
+
+    fn start_tween(&mut self, prop: Prop, to: i32, duration_ms: u32, easing: Easing) {
+        let from = self.get(prop);
+        self.tweens.retain(|t| t.prop != prop);
+        if duration_ms == 0 || from == to {
+            self.set(prop, to);
+            return;
+        }
+        self.tweens.push(Tween { prop, from, to, duration_ms, elapsed_ms: 0, easing });
+    }
+
+    /// Start towards the next waypoint, if any; ends the walk after the
+    /// last.
+    fn next_leg(&mut self) {
+        while let Some(walk) = &mut self.walk {
+            let Some((x, y)) = walk.points.pop_front() else {
+                self.walk = None;
+                return;
+            };
+            let (dx, dy) = ((x - self.x) as f32, (y - self.y) as f32);
+            let ms = ((dx * dx + dy * dy).sqrt() * 1000.0 / walk.speed_px_s as f32) as u32;
+            let easing = walk.easing;
+            self.start_tween(Prop::X, x, ms, easing);
+            self.start_tween(Prop::Y, y, ms, easing);
+            // A leg to where the sprite already is ends at once.
+            if self.tweens.iter().any(|t| matches!(t.prop, Prop::X | Prop::Y)) {
+                return;
+            }
+        }
+    }
+
+    fn get(&self, prop: Prop) -> i32 {
+        match prop {
+            Prop::X => self.x,
+            Prop::Y => self.y,
+            Prop::Opa => self.opa,
+            Prop::Zoom => self.zoom,
+        }
+    }
+
+    fn set(&mut self, prop: Prop, value: i32) {
+        unsafe {
+            match prop {
+                Prop::X => {
+                    self.x = value;
+                    lvgl_sys::lv_obj_set_x(self.widget, value as lvgl_sys::lv_coord_t);
+                    self.place_bubble();
+                }
+                Prop::Y => {
+                    self.y = value;
+                    lvgl_sys::lv_obj_set_y(self.widget, value as lvgl_sys::lv_coord_t);
+                    self.place_bubble();
+                }
+                Prop::Opa => {
+                    self.opa = value.clamp(0, lvgl_sys::LV_OPA_COVER as i32);
+                    lvgl_sys::lv_obj_set_style_opa(
+                        self.widget,
+                        self.opa as lvgl_sys::lv_opa_t,
+                        lvgl_sys::LV_STATE_DEFAULT,
+                    );
+                }
+                Prop::Zoom => {
+                    self.zoom = value.clamp(0, u16::MAX as i32);
+                    lvgl_sys::lv_img_set_zoom(self.widget, self.zoom as u16);
+                }
+            }
+        }
+    }
+
+    fn show(&mut self, frame: usize) {
+        unsafe { lvgl_sys::lv_img_set_src(self.widget, self.frames.src(frame)) };
+    }
+}
+
+impl Drop for AnimatedSprite {
+    fn drop(&mut self) {
+        // Before `frames` can go: the widget points into it.
+        unsafe { lvgl_sys::lv_obj_del(self.widget) };
+    }
+}
+
+/// `true` if `point` (display coordinates) is within `HIT_SLOP` of a pixel
+/// of `img`'s current frame that is not `key`. Undoes the image's zoom.
+unsafe fn frame_hit(img: *mut lvgl_sys::lv_obj_t, point: &lvgl_sys::lv_point_t, key: u16) -> bool {
+    let dsc = lvgl_sys::lv_img_get_src(img) as *const lvgl_sys::lv_img_dsc_t;
+    if dsc.is_null() {
+        return false;
+    }
+    let (w, h) = ((*dsc).header.w() as i32, (*dsc).header.h() as i32);
+    let pixels = core::slice::from_raw_parts((*dsc).data as *const u16, (w * h) as usize);
+    let mut coords: lvgl_sys::lv_area_t = core::mem::zeroed();
+    lvgl_sys::lv_obj_get_coords(img, &mut coords);
+    // Zoom pivots on the centre.
+    let zoom = (lvgl_sys::lv_img_get_zoom(img) as i32).max(1);
+    let (cx, cy) = (coords.x1 as i32 + w / 2, coords.y1 as i32 + h / 2);
+    let px = (point.x as i32 - cx) * lvgl_sys::LV_IMG_ZOOM_NONE as i32 / zoom + w / 2;
+    let py = (point.y as i32 - cy) * lvgl_sys::LV_IMG_ZOOM_NONE as i32 / zoom + h / 2;
+    ((py - HIT_SLOP).max(0)..=(py + HIT_SLOP).min(h - 1)).any(|y| {
+        ((px - HIT_SLOP).max(0)..=(px + HIT_SLOP).min(w - 1)).any(|x| pixels[(y * w + x) as usize] != key)
+    })
+}
+
+/// Answers LVGL's hit test for a tappable sprite; the key colour is the
+/// user data.
+unsafe extern "C" fn hit_test_cb(e: *mut lvgl_sys::lv_event_t) {
+    let info = lvgl_sys::lv_event_get_param(e) as *mut lvgl_sys::lv_hit_test_info_t;
+    let key = lvgl_sys::lv_event_get_user_data(e) as usize as u16;
+    (*info).res = frame_hit(lvgl_sys::lv_event_get_target(e), &*(*info).point, key);
+}
+
+/// Queues a tap for the director. The widget's user data is its sprite
+/// index; the callback's is the scheduler's sprites.
+unsafe extern "C" fn tap_cb(e: *mut lvgl_sys::lv_event_t) {
+    // Not for the end of a swipe that started on the sprite.
+    let mut p: lvgl_sys::lv_point_t = core::mem::zeroed();
+    lvgl_sys::lv_indev_get_point(lvgl_sys::lv_indev_get_act(), &mut p);
+    let (x0, y0) = touch::press_start();
+    if (p.x as i32 - x0).abs() > TAP_SLOP || (p.y as i32 - y0).abs() > TAP_SLOP {
+        return;
+    }
+    let sprites = &*(lvgl_sys::lv_event_get_user_data(e) as *const RefCell<Sprites>);
+    let id = SpriteId((*lvgl_sys::lv_event_get_target(e)).user_data as usize);
+    sprites.borrow_mut().taps.push(id);
+}
+
+/// Build an lv_img_dsc_t for a u16 RGB565 pixel array.
+/// w, h: sprite dimensions in pixels.
+pub fn image_dsc(pixels: &'static [u16], w: u32, h: u32) -> lvgl_sys::lv_img_dsc_t {
+    let mut dsc = lvgl_sys::lv_img_dsc_t::default();
+    dsc.header.set_cf(lvgl_sys::LV_IMG_CF_TRUE_COLOR as u32);
+    dsc.header.set_w(w);
+    dsc.header.set_h(h);
+    dsc.data_size = w * h * core::mem::size_of::<u16>() as u32;
+    dsc.data = pixels.as_ptr() as *const u8;
+    dsc
+}
+
+/// Index of a sprite in its `Scheduler`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteId(usize);
+
+/// Runs every tick, before the sprites advance, with the time passed.
+type Director = Box<dyn FnMut(u32, &mut Stage)>;
+
+struct Sprites {
+    list: Vec<AnimatedSprite>,
+    last_tick: u32,
+    director: Option<Director>,
+    /// Tapped since the last tick, oldest first.
+    taps: Vec<SpriteId>,
+}
+
+/// The sprites of a `Scheduler`, as its director sees them.
+pub struct Stage<'a> {
+    list: &'a mut [AnimatedSprite],
+    taps: &'a [SpriteId],
+}
+
+impl Stage<'_> {
+    pub fn sprite(&mut self, id: SpriteId) -> &mut AnimatedSprite {
+        &mut self.list[id.0]
+    }
+
+    /// Tappable sprites tapped since the last tick, oldest first.
+    pub fn taps(&self) -> &[SpriteId] {
+        self.taps
+    }
+}
+
+/// Steps a set of sprites from one LVGL timer. Created paused.
+pub struct Scheduler {
+    // Boxed: the timer holds a pointer to it.
+    sprites: Box<RefCell<Sprites>>,
+    timer: *mut lvgl_sys::lv_timer_t,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        let sprites = Box::new(RefCell::new(Sprites { list: Vec::new(), last_tick: 0, director: None, taps: Vec::new() }));
+        let timer = unsafe {
+            let t = lvgl_sys::lv_timer_create(
+                Some(tick_cb),
+                TICK_MS,
+                &*sprites as *const RefCell<Sprites> as *mut core::ffi::c_void,
+            );
+            lvgl_sys::lv_timer_pause(t);
+            t
+        };
+        Self { sprites, timer }
+    }
+
+    pub fn add(&mut self, sprite: AnimatedSprite) -> SpriteId {
+        let mut sprites = self.sprites.borrow_mut();
+        sprites.list.push(sprite);
+        SpriteId(sprites.list.len() - 1)
+    }
+
+    /// Report taps on sprite `id` to the director. Only pixels of its
+    /// current frame that are not `key` (the fill around the figure) count,
+    /// give or take `HIT_SLOP`.
+    pub fn set_tappable(&mut self, id: SpriteId, key: u16) {
+        let widget = self.sprites.borrow().list[id.0].widget;
+        unsafe {
+            (*widget).user_data = id.0 as *mut core::ffi::c_void;
+            lvgl_sys::lv_obj_add_flag(widget, lvgl_sys::LV_OBJ_FLAG_CLICKABLE | lvgl_sys::LV_OBJ_FLAG_ADV_HITTEST);
+            lvgl_sys::lv_obj_add_event_cb(
+                widget,
+                Some(hit_test_cb),
+                lvgl_sys::lv_event_code_t_LV_EVENT_HIT_TEST,
+                key as usize as *mut core::ffi::c_void,
+            );
+            lvgl_sys::lv_obj_add_event_cb(
+                widget,
+                Some(tap_cb),
+                lvgl_sys::lv_event_code_t_LV_EVENT_SHORT_CLICKED,
+                &*self.sprites as *const RefCell<Sprites> as *mut core::ffi::c_void,
+            );
+        }
+    }
+
+    /// Call `director` every tick, before the sprites advance, e.g. to play
+    /// a script. It runs only while the scheduler does.
+    pub fn set_director(&mut self, director: impl FnMut(u32, &mut Stage) + 'static) {
+        self.sprites.borrow_mut().director = Some(Box::new(director));
+    }
+
+    /// Start or continue animating. Time spent paused does not count.
+    pub fn resume(&mut self) {
+        let mut sprites = self.sprites.borrow_mut();
+        sprites.last_tick = unsafe { lvgl_sys::lv_tick_get() };
+        // Taps queued just before the last pause are stale by now.
+        sprites.taps.clear();
+        unsafe { lvgl_sys::lv_timer_resume(self.timer) };
+    }
+
+    pub fn pause(&mut self) {
+        unsafe { lvgl_sys::lv_timer_pause(self.timer) };
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        // The timer goes first: it holds a pointer into `sprites`.
+        unsafe { lvgl_sys::lv_timer_del(self.timer) };
+    }
+}
+
+unsafe extern "C" fn tick_cb(timer: *mut lvgl_sys::lv_timer_t) {
+    let sprites = &*((*timer).user_data as *const RefCell<Sprites>);
+    let mut sprites = sprites.borrow_mut();
+    let dt = lvgl_sys::lv_tick_elaps(sprites.last_tick);
+    let sprites = &mut *sprites;
+    sprites.last_tick = sprites.last_tick.wrapping_add(dt);
+    if let Some(director) = &mut sprites.director {
+        director(dt, &mut Stage { list: &mut sprites.list, taps: &sprites.taps });
+    }
+    sprites.taps.clear();
+    for sprite in &mut sprites.list {
+        sprite.advance(dt);
+    }
+}
@@ -0,0 +1,75 @@
+// src/auto_dim.rs
+// Idle-based brightness policy: full brightness while in use, dimmed after
+// `dim_after` without a touch, off after `off_after`. The next touch resets
+// the idle time and with it the brightness.
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoDimConfig {
+    pub dim_after: Duration,
+    pub off_after: Duration,
+    /// Brightness while active (0..=255).
+    pub full: u8,
+    /// Brightness while dimmed.
+    pub dimmed: u8,
+}
+
+impl Default for AutoDimConfig {
+    fn default() -> Self {
+        AutoDimConfig {
+            dim_after: Duration::from_secs(15),
+            off_after: Duration::from_secs(30),
+            full: 0xFF,
+            dimmed: 0x30,
+        }
+    }
+}
+
+pub struct AutoDim {
+    cfg: AutoDimConfig,
+    level: Option<u8>,
+}
+
+impl AutoDim {
+    pub fn new(cfg: AutoDimConfig) -> Self {
+        Self { cfg, level: None }
+    }
+
+    /// Change the active brightness. Takes effect on the next `update`
+    /// if the panel is currently at full or dimmed brightness.
+    pub fn set_full(&mut self, level: u8) {
+        if level == self.cfg.full {
+            return;
+        }
+        if self.level == Some(self.cfg.full) || self.level == Some(self.dimmed()) {
+            self.level = None;
+        }
+        self.cfg.full = level;
+    }
+
+    /// Dimming never brightens: a full level set below `cfg.dimmed` is
+    /// used while dimmed too.
+    fn dimmed(&self) -> u8 {
+        self.cfg.dimmed.min(self.cfg.full)
+    }
+
+    /// Brightness `idle` calls for: `Some(level)` when it differs from the
+    /// last `commit`ted level (always before the first), `None` otherwise.
+    pub fn update(&self, idle: Duration) -> Option<u8> {
+        let level = if idle >= self.cfg.off_after {
+            0
+        } else if idle >= self.cfg.dim_after {
+            self.dimmed()
+        } else {
+            self.cfg.full
+        };
+        (self.level != Some(level)).then_some(level)
+    }
+
+    /// Record that the panel is now at `level`. Call only once it has been
+    /// set, so a failed write is retried on the next `update`.
+    pub fn commit(&mut self, level: u8) {
+        self.level = Some(level);
+    }
+}
@@ -0,0 +1,517 @@
+// src/display.rs
+// Safe wrapper around the C lcd_driver component (components/lcd_driver).
+// `Display` owns the panel; an async flush borrows the display and the
+// pixels and returns a `Flush` guard that holds both until the DMA transfer
+// is done.
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use esp_idf_svc::sys::{self, esp, EspError};
+
+use crate::panel::{self, PanelProfile};
+use crate::safe_area;
+
+mod ffi {
+    /// `lcd_init_cmd_t`
+    #[repr(C)]
+    pub struct InitCmd {
+        pub cmd: i32,
+        pub data: *const core::ffi::c_void,
+        pub data_bytes: usize,
+        pub delay_ms: u32,
+    }
+
+    /// `lcd_panel_config_t`
+    #[repr(C)]
+    pub struct PanelConfig {
+        pub name: *const core::ffi::c_char,
+        pub init_cmds: *const InitCmd,
+        pub init_cmds_size: u16,
+        pub x_gap: i32,
+        pub y_gap: i32,
+    }
+
+    extern "C" {
+        pub fn lcd_read_id() -> u8;
+        pub fn lcd_driver_init(panel: *const PanelConfig) -> i32;
+        pub fn lcd_draw_bitmap_async(x1: i32, y1: i32, x2: i32, y2: i32, data: *const core::ffi::c_void);
+        pub fn lcd_wait_flush_done();
+        pub fn lcd_set_brightness(level: u8) -> i32;
+        pub fn lcd_set_hbm_brightness(level: u8) -> i32;
+        pub fn lcd_te_enable(gpio: i32) -> i32;
+        pub fn lcd_te_disable();
+        pub fn lcd_wait_te(timeout_ms: u32) -> bool;
+    }
+}
+
+pub const WIDTH: u32 = 466;
+pub const HEIGHT: u32 = 466;
+
+/// Longest wait for a TE pulse. The panel refreshes at ~60 Hz, so a missed
+/// pulse costs at most one refresh plus FreeRTOS tick rounding.
+const TE_TIMEOUT_MS: u32 = 20;
+
+/// Consecutive TE timeouts after which TE sync is switched off, so a wrong
+/// pin or a panel without TE output does not halve the frame rate.
+const TE_MAX_MISSES: u8 = 10;
+
+/// Rows per band when clipping a flush to the visible circle. Each band is
+/// one panel transfer, trimmed to the circle's widest span over its rows.
+/// Summing `visible_band` over a full frame: 16 rows skip 18.6% of its
+/// pixels in 30 transfers; per-row spans would skip 21.3% in 466.
+const BAND_ROWS: i32 = 16;
+
+// lcd_driver keeps a single panel handle: only one `Display` may exist.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// A panel region, `x1..x2` × `y1..y2` (end-exclusive, like lcd_draw_bitmap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Area {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
+}
+
+impl Area {
+    pub fn pixels(&self) -> usize {
+        ((self.x2 - self.x1).max(0) * (self.y2 - self.y1).max(0)) as usize
+    }
+
+    /// From an LVGL area (inclusive corners), clipped to the panel.
+    fn from_lv(a: &lvgl_sys::lv_area_t) -> Self {
+        Area {
+            x1: (a.x1 as i32).max(0),
+            y1: (a.y1 as i32).max(0),
+            x2: (a.x2 as i32 + 1).min(WIDTH as i32),
+            y2: (a.y2 as i32 + 1).min(HEIGHT as i32),
+        }
+    }
+
+    /// The part of rows `y1..y2` of this area that falls inside the visible
+    /// circle, or `None` if it is all in the invisible corners.
+    fn visible_band(&self, y1: i32, y2: i32) -> Option<Area> {
+        let (mut x1, mut x2) = (WIDTH as i32, 0);
+        for y in y1..y2 {
+            let (a, b) = safe_area::row_span(y);
+            x1 = x1.min(a);
+            x2 = x2.max(b);
+        }
+        // The panel wants even column addresses: widen to even bounds, but
+        // never past the pixels we actually have.
+        let x1 = (x1 & !1).max(self.x1);
+        let x2 = ((x2 + 1) & !1).min(self.x2);
+        (x1 < x2).then_some(Area { x1, y1, x2, y2 })
+    }
+
+    fn check(&self, pixels: &[u16]) {
+        assert!(
+            self.x1 >= 0 && self.y1 >= 0 && self.x2 <= WIDTH as i32 && self.y2 <= HEIGHT as i32,
+            "area {self:?} outside the panel"
+        );
+        assert!(pixels.len() >= self.pixels(), "pixel buffer too small for {self:?}");
+    }
+}
+
+/// The AMOLED panel. Owning it is the only way to draw.
+pub struct Display {
+    profile: &'static PanelProfile,
+    te_sync: bool,
+    te_misses: u8,
+    // A frame is being flushed: its first chunk has gone out, its last not yet.
+    in_frame: bool,
+}
+
+impl Display {
+    /// Power up and initialise the panel with `profile`, or with the profile
+    /// matching the panel's ID if `None`. Fails with `ESP_ERR_INVALID_STATE`
+    /// if a `Display` already exists.
+    pub fn init(profile: Option<&'static PanelProfile>) -> Result<Display, EspError> {
+        if TAKEN.swap(true, Ordering::AcqRel) {
+            return Err(EspError::from(sys::ESP_ERR_INVALID_STATE as _).unwrap());
+        }
+        // SAFETY: called once, guarded by TAKEN. The ID read also resets the
+        // panel and must precede lcd_driver_init either way.
+        let id = unsafe { ffi::lcd_read_id() };
+        let profile = profile.unwrap_or_else(|| panel::detect(id));
+        log::info!("panel {} (ID 0x{id:02x}, gap {:?}, HBM {:?})", profile.name, profile.gap, profile.hbm);
+
+        // The C side only reads the tables during init: temporary copies
+        // in its layout are enough.
+        let cmds: Vec<ffi::InitCmd> = profile
+            .init_cmds
+            .iter()
+            .map(|c| ffi::InitCmd {
+                cmd: c.cmd as i32,
+                data: if c.data.is_empty() { core::ptr::null() } else { c.data.as_ptr() as *const _ },
+                data_bytes: c.data.len(),
+                delay_ms: c.delay_ms,
+            })
+            .collect();
+        let name = std::ffi::CString::new(profile.name).unwrap_or_default();
+        let config = ffi::PanelConfig {
+            name: name.as_ptr(),
+            init_cmds: cmds.as_ptr(),
+            init_cmds_size: cmds.len() as u16,
+            x_gap: profile.gap.0,
+            y_gap: profile.gap.1,
+        };
+        // SAFETY: `config` and everything it points to outlive the call;
+        // the HBM register is only written once the panel is up.
+        let ready = esp!(unsafe { ffi::lcd_driver_init(&config) }).and_then(|()| match profile.hbm {
+            Some(level) => esp!(unsafe { ffi::lcd_set_hbm_brightness(level) }),
+            None => Ok(()),
+        });
+        if let Err(e) = ready {
+            TAKEN.store(false, Ordering::Release);
+            return Err(e);
+        }
+        Ok(Display { profile, te_sync: false, te_misses: 0, in_frame: false })
+    }
+
+    /// The profile the panel was initialised with.
+    pub fn profile(&self) -> &'static PanelProfile {
+        self.profile
+    }
+
+    /// Panel brightness, 0 = off, 255 = the profile's `max_brightness`
+    /// (the level after `init`).
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), EspError> {
+        let raw = (level as u16 * self.profile.max_brightness as u16 / 255) as u8;
+        // SAFETY: the panel is initialised while a `Display` exists.
+        esp!(unsafe { ffi::lcd_set_brightness(raw) })
+    }
+
+    /// Start sending RGB565 pixels (byte-swapped, row-major) and return at
+    /// once. Only the pixels inside the visible circle go out: the area is
+    /// split into bands of `BAND_ROWS` rows, each trimmed
+    /// to the circle. Each band is compacted in place while the previous
+    /// one is on the wire, so `pixels` is clobbered. The driver keeps one
+    /// transfer in flight: queuing a band waits for the one before.
+    ///
+    /// # Safety
+    ///
+    /// The returned `Flush` must be waited for or dropped, not leaked:
+    /// `mem::forget` would end the borrow of `pixels` while the DMA may
+    /// still be reading them.
+    pub unsafe fn draw_async_visible<'a>(&'a mut self, area: Area, pixels: &'a mut [u16]) -> Flush<'a> {
+        area.check(pixels);
+        let w = (area.x2 - area.x1) as usize;
+        // Compaction only ever moves pixels towards the start of the buffer,
+        // and behind the band in flight: `out` never passes a source row.
+        let mut out = 0;
+        for y in (area.y1..area.y2).step_by(BAND_ROWS as usize) {
+            let Some(band) = area.visible_band(y, (y + BAND_ROWS).min(area.y2)) else {
+                continue;
+            };
+            let bw = (band.x2 - band.x1) as usize;
+            let start = out;
+            for row in band.y1..band.y2 {
+                let src = (row - area.y1) as usize * w + (band.x1 - area.x1) as usize;
+                pixels.copy_within(src..src + bw, out);
+                out += bw;
+            }
+            // SAFETY: the `Flush` guard borrows `pixels` until the last band
+            // has completed (the caller does not leak it); compaction only
+            // writes past the band in flight.
+            unsafe {
+                ffi::lcd_draw_bitmap_async(band.x1, band.y1, band.x2, band.y2, pixels[start..].as_ptr() as *const _)
+            };
+        }
+        Flush { display: Some(self), _pixels: PhantomData }
+    }
+
+    /// Send the visible part of `area` of a `stride`-wide framebuffer that
+    /// the panel DMA cannot read (e.g. PSRAM), staging rows through the two
+    /// `bounce` buffers: one is filled while the other is on the wire.
+    /// Blocks until done.
+    fn draw_strided(&mut self, fb: &[u16], stride: usize, area: Area, bounce: &mut [&'static mut [u16]; 2]) {
+        let w = (area.x2 - area.x1) as usize;
+        if w == 0 || area.y2 <= area.y1 {
+            return;
+        }
+        let rows_per_chunk = bounce[0].len().min(bounce[1].len()) / w;
+        assert!(rows_per_chunk > 0, "bounce buffers narrower than {area:?}");
+
+        let mut in_flight = false;
+        let chunks = (area.y1..area.y2)
+            .step_by(rows_per_chunk)
+            .filter_map(|y| area.visible_band(y, (y + rows_per_chunk as i32).min(area.y2)));
+        for (i, chunk) in chunks.enumerate() {
+            // This buffer's previous transfer (chunk i - 2) finished before
+            // chunk i - 1 was started.
+            let buf = &mut *bounce[i % 2];
+            let cw = (chunk.x2 - chunk.x1) as usize;
+            for (r, y) in (chunk.y1..chunk.y2).enumerate() {
+                let src = y as usize * stride + chunk.x1 as usize;
+                buf[r * cw..(r + 1) * cw].copy_from_slice(&fb[src..src + cw]);
+            }
+            chunk.check(buf);
+            // SAFETY: at most one transfer is in flight and each bounce buffer
+            // is only rewritten after its transfer has been waited for; the
+            // last transfer is waited for before returning.
+            unsafe {
+                if in_flight {
+                    ffi::lcd_wait_flush_done();
+                }
+                ffi::lcd_draw_bitmap_async(chunk.x1, chunk.y1, chunk.x2, chunk.y2, buf.as_ptr() as *const _);
+            }
+            in_flight = true;
+        }
+        if in_flight {
+            // SAFETY: see above.
+            unsafe { ffi::lcd_wait_flush_done() };
+        }
+    }
+
+    /// Synchronise flushing to the panel refresh: the first chunk of each
+    /// frame waits for the TE pulse on `gpio`. `None` turns sync off.
+    pub fn set_te_sync(&mut self, gpio: Option<i32>) -> Result<(), EspError> {
+        // SAFETY: plain calls into lcd_driver; the panel is initialised.
+        unsafe {
+            if self.te_sync {
+                ffi::lcd_te_disable();
+                self.te_sync = false;
+            }
+            if let Some(gpio) = gpio {
+                esp!(ffi::lcd_te_enable(gpio))?;
+                self.te_sync = true;
+                self.te_misses = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait for the panel's V-blank if TE sync is on.
+    fn wait_te(&mut self) {
+        if !self.te_sync {
+            return;
+        }
+        // SAFETY: TE sync is enabled.
+        if unsafe { ffi::lcd_wait_te(TE_TIMEOUT_MS) } {
+            self.te_misses = 0;
+            return;
+        }
+        self.te_misses += 1;
+        if self.te_misses >= TE_MAX_MISSES {
+            log::warn!("no TE pulses, disabling TE sync");
+            let _ = self.set_te_sync(None);
+        }
+    }
+}
+
+/// An in-flight DMA transfer. Waits for completion on `wait` or drop.
+#[must_use = "dropping a Flush blocks until the transfer is done"]
+pub struct Flush<'a> {
+    display: Option<&'a mut Display>,
+    _pixels: PhantomData<&'a mut [u16]>,
+}
+
+impl<'a> Flush<'a> {
+    /// Block until the transfer is done and hand the display back.
+    pub fn wait(mut self) -> &'a mut Display {
+        // SAFETY: transfers were queued by `draw_async_visible`.
+        unsafe { ffi::lcd_wait_flush_done() };
+        self.display.take().unwrap()
+    }
+}
+
+impl Drop for Flush<'_> {
+    fn drop(&mut self) {
+        if self.display.is_some() {
+            // SAFETY: as in `wait`.
+            unsafe { ffi::lcd_wait_flush_done() };
+        }
+    }
+}
+
+/// Allocate a zeroed pixel buffer with the given heap capabilities that
+/// lives forever. Pass `MALLOC_CAP_DMA` for anything handed to the panel:
+/// esp-lcd rejects PSRAM buffers (`esp_ptr_dma_capable`).
+fn alloc_pixels(pixels: usize, caps: u32) -> Result<&'static mut [u16], EspError> {
+    // SAFETY: the allocation is zeroed, sized for `pixels` u16s and never freed.
+    unsafe {
+        let ptr = sys::heap_caps_calloc(pixels, core::mem::size_of::<u16>(), caps) as *mut u16;
+        if ptr.is_null() {
+            return Err(EspError::from(sys::ESP_ERR_NO_MEM as _).unwrap());
+        }
+        Ok(core::slice::from_raw_parts_mut(ptr, pixels))
+    }
+}
+
+// ── LVGL glue ────────────────────────────────────────────────────────────────
+
+const FRAME_PIXELS: usize = (WIDTH * HEIGHT) as usize;
+
+/// Where LVGL renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// Two internal-SRAM DMA buffers of `rows` rows each; LVGL renders dirty
+    /// areas in chunks and each chunk is sent straight from its buffer.
+    Partial { rows: usize },
+    /// One full-frame framebuffer in PSRAM (LVGL direct mode). Dirty areas
+    /// are copied to the panel through two small internal bounce buffers of
+    /// `bounce_rows` rows each.
+    FullFrame { bounce_rows: usize },
+}
+
+impl DisplayMode {
+    /// `FullFrame` if enough PSRAM is free for the framebuffer, else `Partial`.
+    pub fn auto() -> Self {
+        // SAFETY: read-only heap query.
+        let psram = unsafe { sys::heap_caps_get_largest_free_block(sys::MALLOC_CAP_SPIRAM) };
+        if psram >= FRAME_PIXELS * core::mem::size_of::<u16>() {
+            DisplayMode::FullFrame { bounce_rows: 16 }
+        } else {
+            DisplayMode::Partial { rows: 100 }
+        }
+    }
+}
+
+enum FlushState {
+    Idle(&'static mut Display),
+    Busy(Flush<'static>),
+}
+
+impl FlushState {
+    fn into_idle(self) -> &'static mut Display {
+        match self {
+            FlushState::Idle(d) => d,
+            FlushState::Busy(f) => f.wait(),
+        }
+    }
+}
+
+// Display state used by the flush callbacks: idle, or a transfer still in
+// flight from the previous call.
+static LVGL_FLUSH: Mutex<Option<FlushState>> = Mutex::new(None);
+
+// Internal-SRAM bounce buffers for `DisplayMode::FullFrame`.
+static BOUNCE: Mutex<Option<[&'static mut [u16]; 2]>> = Mutex::new(None);
+
+/// Allocate the draw buffers for `mode` and register the display with LVGL.
+/// Call after `lv_init`. From here on the display is driven by LVGL's flush
+/// callback; use `with_display` to reach it.
+pub fn register_lvgl(display: Display, mode: DisplayMode) -> Result<(), EspError> {
+    let (buf1, buf2, buf_pixels) = match mode {
+        DisplayMode::Partial { rows } => {
+            let pixels = WIDTH as usize * rows;
+            let buf1 = alloc_pixels(pixels, sys::MALLOC_CAP_DMA)?;
+            let buf2 = alloc_pixels(pixels, sys::MALLOC_CAP_DMA)?;
+            (buf1.as_mut_ptr(), buf2.as_mut_ptr(), pixels)
+        }
+        DisplayMode::FullFrame { bounce_rows } => {
+            let pixels = WIDTH as usize * bounce_rows;
+            *BOUNCE.lock().unwrap() = Some([
+                alloc_pixels(pixels, sys::MALLOC_CAP_DMA)?,
+                alloc_pixels(pixels, sys::MALLOC_CAP_DMA)?,
+            ]);
+            let fb = alloc_pixels(FRAME_PIXELS, sys::MALLOC_CAP_SPIRAM)?;
+            (fb.as_mut_ptr(), core::ptr::null_mut(), FRAME_PIXELS)
+        }
+    };
+    *LVGL_FLUSH.lock().unwrap() = Some(FlushState::Idle(Box::leak(Box::new(display))));
+
+    // SAFETY: LVGL is initialised; the descriptors are leaked because LVGL
+    // 8.x stores pointers to them, not copies.
+    unsafe {
+        let disp_buf: &'static mut lvgl_sys::lv_disp_draw_buf_t =
+            Box::leak(Box::new(core::mem::zeroed()));
+        lvgl_sys::lv_disp_draw_buf_init(disp_buf, buf1 as *mut _, buf2 as *mut _, buf_pixels as u32);
+
+        let disp_drv: &'static mut lvgl_sys::lv_disp_drv_t =
+            Box::leak(Box::new(core::mem::zeroed()));
+        lvgl_sys::lv_disp_drv_init(disp_drv);
+        disp_drv.hor_res = WIDTH as lvgl_sys::lv_coord_t;
+        disp_drv.ver_res = HEIGHT as lvgl_sys::lv_coord_t;
+        disp_drv.draw_buf = disp_buf;
+        match mode {
+            DisplayMode::Partial { .. } => disp_drv.flush_cb = Some(partial_flush_cb),
+            DisplayMode::FullFrame { .. } => {
+                disp_drv.set_direct_mode(1);
+                disp_drv.flush_cb = Some(full_frame_flush_cb);
+            }
+        }
+        lvgl_sys::lv_disp_drv_register(disp_drv);
+    }
+    log::info!("LVGL display registered ({mode:?})");
+    Ok(())
+}
+
+/// Run `f` on the display attached to LVGL, after any in-flight transfer.
+/// Must be called on the LVGL thread, outside `lv_timer_handler`.
+pub fn with_display<R>(f: impl FnOnce(&mut Display) -> R) -> R {
+    let mut state = LVGL_FLUSH.lock().unwrap();
+    let display = state.take().expect("display not attached to LVGL").into_idle();
+    let result = f(display);
+    *state = Some(FlushState::Idle(display));
+    result
+}
+
+// Partial-mode flush callback — double-buffer async DMA pattern:
+//   1. Wait for the previous async DMA to finish (no-op on first call).
+//   2. On the first chunk of a frame, wait for TE if TE sync is on.
+//   3. Start async DMA for the visible bands of the current buffer.
+//   4. Immediately signal flush_ready so LVGL can render into the OTHER buffer
+//      while the DMA for this buffer runs in the background.
+// lv_area_t coords are inclusive; `Area` is end-exclusive.
+unsafe extern "C" fn partial_flush_cb(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    area: *const lvgl_sys::lv_area_t,
+    color_p: *mut lvgl_sys::lv_color_t,
+) {
+    let area = Area::from_lv(&*area);
+    // SAFETY: LVGL rendered `area` into `color_p`, row-major.
+    let pixels = core::slice::from_raw_parts_mut(color_p as *mut u16, area.pixels());
+
+    let mut state = LVGL_FLUSH.lock().unwrap();
+    let display = state.take().expect("display not attached to LVGL").into_idle();
+    // Start each frame right after V-blank so the panel scan-out never
+    // overtakes the write pointer mid-frame.
+    if !display.in_frame {
+        display.wait_te();
+    }
+    display.in_frame = !lvgl_sys::lv_disp_flush_is_last(disp_drv);
+    // SAFETY: `color_p` points into one of the two leaked draw buffers.
+    // LVGL renders into the other one next and does not touch this one
+    // again until the next flush has waited for this transfer: the `Flush`
+    // stays in LVGL_FLUSH until then and is never leaked.
+    *state = Some(FlushState::Busy(display.draw_async_visible(area, pixels)));
+    // Signal LVGL immediately: with two buffers, this swaps buf_act so LVGL
+    // can render the next chunk into the other buffer concurrently with the DMA.
+    lvgl_sys::lv_disp_flush_ready(disp_drv);
+}
+
+// Full-frame (direct mode) flush callback. LVGL has already rendered every
+// dirty area into the PSRAM framebuffer and calls this once per area, always
+// with the whole screen as `area`. Nothing is sent until the last call; then
+// each dirty area is copied out through the bounce buffers.
+unsafe extern "C" fn full_frame_flush_cb(
+    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
+    _area: *const lvgl_sys::lv_area_t,
+    color_p: *mut lvgl_sys::lv_color_t,
+) {
+    if lvgl_sys::lv_disp_flush_is_last(disp_drv) {
+        // SAFETY: in direct mode `color_p` is the full-frame buffer, and LVGL
+        // does not render again until `lv_disp_flush_ready`.
+        let fb = core::slice::from_raw_parts(color_p as *const u16, FRAME_PIXELS);
+        let disp = &*lvgl_sys::lv_disp_get_default();
+
+        let mut state = LVGL_FLUSH.lock().unwrap();
+        let display = state.take().expect("display not attached to LVGL").into_idle();
+        let mut bounce = BOUNCE.lock().unwrap();
+        let bounce = bounce.as_mut().expect("bounce buffers not allocated");
+        display.wait_te();
+        // Invalidated areas stay valid until the refresh completes; joined
+        // ones are covered by another entry.
+        for i in 0..disp.inv_p as usize {
+            if disp.inv_area_joined[i] == 0 {
+                display.draw_strided(fb, WIDTH as usize, Area::from_lv(&disp.inv_areas[i]), bounce);
+            }
+        }
+        *state = Some(FlushState::Idle(display));
+    }
+    lvgl_sys::lv_disp_flush_ready(disp_drv);
+}
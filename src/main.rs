@@ -1,166 +1,81 @@
-use std::time::Duration;
-
-mod ft3168;
-mod safe_area;
+use std::time::{Duration, Instant};
+
+mod anim;
+mod auto_dim;
+mod display;
+mod notifications;
+mod panel;
+mod screens;
+mod settings;
 mod spaceship;
 mod sprites;
+mod timeline;
+mod touch;
+mod touch_bus;
+mod touch_transform;
 
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-
+use esp_idf_svc::hal::gpio::{IOPin, PinDriver, Pull};
 use esp_idf_svc::hal::i2c::{I2cConfig, I2cDriver};
 use esp_idf_svc::hal::peripherals::Peripherals;
 use esp_idf_svc::hal::units::Hertz;
+use esp_idf_svc::nvs::EspDefaultNvsPartition;
 
-extern "C" {
-    fn lcd_driver_init() -> i32;
-    #[allow(dead_code)]
-    fn lcd_draw_bitmap(x1: i32, y1: i32, x2: i32, y2: i32, data: *const core::ffi::c_void);
-    fn lcd_draw_bitmap_async(x1: i32, y1: i32, x2: i32, y2: i32, data: *const core::ffi::c_void);
-    fn lcd_wait_flush_done();
-}
-
-const LCD_W: u32 = 466;
-const LCD_H: u32 = 466;
-const DRAW_BUF_PIXELS: usize = LCD_W as usize * 100; // 100 rows (~91KB internal DMA SRAM)
+use screens::transition::{Easing, Transition, TransitionKind};
+use touch_transform::{Rotation, TouchTransform};
+use ui_core::safe_area;
 
-// Touch state written by the main loop, read by the LVGL indev callback.
-// Both run on the same thread (indev cb is called inside lv_timer_handler),
-// so Relaxed ordering is sufficient.
-static TOUCH_X: AtomicI32 = AtomicI32::new(0);
-static TOUCH_Y: AtomicI32 = AtomicI32::new(0);
-static TOUCH_PRESSED: AtomicBool = AtomicBool::new(false);
+// GPIO wired to the panel's tearing-effect output. The board schematic
+// does not confirm one yet, so TE sync stays off until it is set here.
+const LCD_TE_GPIO: Option<i32> = None;
 
-// Screen object pointers. Written once during init, read by gesture callback.
-static mut SCREEN1: *mut lvgl_sys::lv_obj_t = core::ptr::null_mut();
-static mut SCREEN2: *mut lvgl_sys::lv_obj_t = core::ptr::null_mut();
+// How the digitizer is mounted relative to the displayed content. Only used
+// until an on-screen calibration is stored, which keeps its own.
+const TOUCH_ROTATION: Rotation = Rotation::Deg0;
+const TOUCH_MIRROR_X: bool = false;
+const TOUCH_MIRROR_Y: bool = false;
 
-// Spaceship animation state — single-thread, written once during init
-static mut CREW_WIDGETS: [*mut lvgl_sys::lv_obj_t; 3] = [core::ptr::null_mut(); 3];
-static mut CMD_WIDGET: *mut lvgl_sys::lv_obj_t = core::ptr::null_mut();
-static mut BLINK_WIDGET: *mut lvgl_sys::lv_obj_t = core::ptr::null_mut();
+// Give up on a touch controller transaction after this long.
+const TOUCH_I2C_TIMEOUT: Duration = Duration::from_millis(100);
 
-static mut CREW_FRAME: u8 = 0;
-static mut CMD_FRAME: u8 = 0;
-static mut BLINK_FRAME: u8 = 0;
+// Light-sleep the SoC after this long without a touch; a touch wakes it.
+// Longer than the auto-dim off delay, so the panel is already dark.
+const IDLE_SLEEP: Duration = Duration::from_secs(60);
 
-// Image descriptors — must be 'static (LVGL holds raw pointers)
-static mut CREW_DSC_A: *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut CREW_DSC_B: *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut CMD_DSC_A:  *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut CMD_DSC_B:  *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut CMD_DSC_C:  *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut BLINK_DSC_A: *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut BLINK_DSC_B: *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
-static mut BG_DSC: *const lvgl_sys::lv_img_dsc_t = core::ptr::null();
+// After a failed light sleep, stay awake this long before trying again.
+const SLEEP_RETRY: Duration = Duration::from_secs(30);
 
-// LVGL flush callback — double-buffer async DMA pattern:
-//   1. Wait for the previous async DMA to finish (no-op on first call).
-//   2. Start a new async DMA for the current buffer.
-//   3. Immediately signal flush_ready so LVGL can render into the OTHER buffer
-//      while the DMA for this buffer runs in the background.
-// lv_area_t coords are inclusive; lcd_draw_bitmap expects exclusive x2/y2.
-unsafe extern "C" fn lvgl_flush_cb(
-    disp_drv: *mut lvgl_sys::lv_disp_drv_t,
-    area: *const lvgl_sys::lv_area_t,
-    color_p: *mut lvgl_sys::lv_color_t,
-) {
-    lcd_wait_flush_done();
-    let x1 = (*area).x1 as i32;
-    let y1 = (*area).y1 as i32;
-    let x2 = (*area).x2 as i32 + 1;
-    let y2 = (*area).y2 as i32 + 1;
-    lcd_draw_bitmap_async(x1, y1, x2, y2, color_p as *const _);
-    // Signal LVGL immediately: with two buffers, this swaps buf_act so LVGL
-    // can render the next chunk into the other buffer concurrently with the DMA.
-    lvgl_sys::lv_disp_flush_ready(disp_drv);
-}
+// Free a screen's LVGL objects, timers and image descriptors after this long
+// without visiting it.
+const SCREEN_TEARDOWN: Duration = Duration::from_secs(30);
 
 /// LVGL input device read callback. Called by lv_timer_handler() on every tick.
-/// Reads touch state from the atomics updated in the main loop.
+/// Reads the touch state published by the touch task.
 unsafe extern "C" fn lvgl_touch_cb(
     _drv: *mut lvgl_sys::lv_indev_drv_t,
     data: *mut lvgl_sys::lv_indev_data_t,
 ) {
-    if TOUCH_PRESSED.load(Ordering::Relaxed) {
-        (*data).point.x = TOUCH_X.load(Ordering::Relaxed) as lvgl_sys::lv_coord_t;
-        (*data).point.y = TOUCH_Y.load(Ordering::Relaxed) as lvgl_sys::lv_coord_t;
+    let touch = touch::state();
+    if touch.pressed {
+        (*data).point.x = touch.x as lvgl_sys::lv_coord_t;
+        (*data).point.y = touch.y as lvgl_sys::lv_coord_t;
         (*data).state = lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED;
     } else {
         (*data).state = lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED;
     }
 }
 
-/// Gesture event callback attached to both screens.
-/// Swipe LEFT  → load screen 2 (if on screen 1).
-/// Swipe RIGHT → load screen 1 (if on screen 2).
-unsafe extern "C" fn gesture_cb(e: *mut lvgl_sys::lv_event_t) {
-    let indev = lvgl_sys::lv_indev_get_act();
-    if indev.is_null() {
-        return;
-    }
-    let dir = lvgl_sys::lv_indev_get_gesture_dir(indev); // returns lv_dir_t = u8
-    let active = lvgl_sys::lv_disp_get_scr_act(lvgl_sys::lv_disp_get_default());
-
-    if dir == lvgl_sys::LV_DIR_LEFT as lvgl_sys::lv_dir_t && active == SCREEN1 {
-        lvgl_sys::lv_scr_load_anim(
-            SCREEN2,
-            lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_MOVE_LEFT,
-            150,
-            0,
-            false,
-        );
-    } else if dir == lvgl_sys::LV_DIR_RIGHT as lvgl_sys::lv_dir_t && active == SCREEN2 {
-        lvgl_sys::lv_scr_load_anim(
-            SCREEN1,
-            lvgl_sys::lv_scr_load_anim_t_LV_SCR_LOAD_ANIM_MOVE_RIGHT,
-            150,
-            0,
-            false,
-        );
-    }
-
-    // Suppress unused parameter warning
-    let _ = e;
-}
-
-/// Build an lv_img_dsc_t for a u16 RGB565 pixel array.
-/// w, h: sprite dimensions in pixels.
-fn make_dsc(pixels: &'static [u16], w: u32, h: u32) -> lvgl_sys::lv_img_dsc_t {
-    let mut dsc = lvgl_sys::lv_img_dsc_t::default();
-    dsc.header.set_cf(lvgl_sys::LV_IMG_CF_TRUE_COLOR as u32);
-    dsc.header.set_w(w);
-    dsc.header.set_h(h);
-    dsc.data_size = (w * h * core::mem::size_of::<u16>() as u32) as u32;
-    dsc.data = pixels.as_ptr() as *const u8;
-    dsc
-}
-
-/// Crew animation timer — fires every 600 ms, cycles through 2 frames.
-/// All 3 crew widgets share the same sprite frames (same body shape).
-unsafe extern "C" fn crew_timer_cb(_timer: *mut lvgl_sys::lv_timer_t) {
-    CREW_FRAME = 1 - CREW_FRAME;
-    let src = if CREW_FRAME == 0 { CREW_DSC_A } else { CREW_DSC_B };
-    for i in 0..3 {
-        lvgl_sys::lv_img_set_src(CREW_WIDGETS[i], src as *const _);
-    }
-}
-
-/// Commander animation timer — fires every 800 ms, cycles A→B→C→A.
-unsafe extern "C" fn cmd_timer_cb(_timer: *mut lvgl_sys::lv_timer_t) {
-    CMD_FRAME = (CMD_FRAME + 1) % 3;
-    let src = match CMD_FRAME {
-        0 => CMD_DSC_A,
-        1 => CMD_DSC_B,
-        _ => CMD_DSC_C,
+/// Handle a gesture recognised by the FT3168 gesture engine.
+/// Must be called on the LVGL thread.
+fn on_hw_gesture(g: ft3168::Gesture) {
+    let dir = match g {
+        ft3168::Gesture::SwipeLeft => lvgl_sys::LV_DIR_LEFT,
+        ft3168::Gesture::SwipeRight => lvgl_sys::LV_DIR_RIGHT,
+        ft3168::Gesture::SwipeUp => lvgl_sys::LV_DIR_TOP,
+        ft3168::Gesture::SwipeDown => lvgl_sys::LV_DIR_BOTTOM,
+        ft3168::Gesture::LongPress => return screens::long_press(),
+        ft3168::Gesture::DoubleTap => return,
     };
-    lvgl_sys::lv_img_set_src(CMD_WIDGET, src as *const _);
-}
-
-/// Console blink timer — fires every 1200 ms, toggles between two colors.
-unsafe extern "C" fn blink_timer_cb(_timer: *mut lvgl_sys::lv_timer_t) {
-    BLINK_FRAME = 1 - BLINK_FRAME;
-    let src = if BLINK_FRAME == 0 { BLINK_DSC_A } else { BLINK_DSC_B };
-    lvgl_sys::lv_img_set_src(BLINK_WIDGET, src as *const _);
+    screens::navigate(dir as lvgl_sys::lv_dir_t);
 }
 
 fn main() {
@@ -178,55 +93,70 @@ fn main() {
         &i2c_config,
     )
     .unwrap();
-    let mut ft3168 = ft3168::Ft3168::new(i2c);
+    let mut ft3168 = ft3168::Ft3168::new(touch_bus::TouchBus::new(i2c, TOUCH_I2C_TIMEOUT))
+        .with_timeout_check(touch_bus::BusError::is_timeout);
+    std::thread::sleep(ft3168::STARTUP_DELAY);
     ft3168.init().expect("FT3168 init failed");
-    log::info!("FT3168 touch controller ready");
+    ft3168
+        .set_interrupt_mode(ft3168::InterruptMode::Trigger)
+        .expect("FT3168 interrupt mode failed");
+    ft3168
+        .enable_gestures(ft3168::GestureSet::ALL)
+        .expect("FT3168 gesture enable failed");
+    // Let the controller drop to its slow monitor scan when nobody is touching.
+    ft3168
+        .set_auto_monitor(Some(ft3168::AutoMonitor { idle_secs: 5, scan_period: 0x28 }))
+        .expect("FT3168 auto-monitor failed");
+
+    let mut tp_int = PinDriver::input(peripherals.pins.gpio15.downgrade()).unwrap(); // TP_INT
+    tp_int.set_pull(Pull::Up).unwrap();
+    touch::spawn(ft3168, tp_int, true, ui_core::touch_filter::FilterConfig::default());
+
+    // Stored touch calibration (rotation, mirroring, affine correction).
+    let nvs = EspDefaultNvsPartition::take().unwrap();
+    let stored = match touch_transform::init_storage(nvs) {
+        Ok(t) => t,
+        Err(e) => {
+            log::warn!("touch calibration storage unavailable: {e}");
+            None
+        }
+    };
+    log::info!("FT3168 touch controller ready (interrupt-driven)");
 
     // ── 1. Hardware init ──────────────────────────────────────────────────────
-    let ret = unsafe { lcd_driver_init() };
-    assert_eq!(ret, 0, "lcd_driver_init failed: {ret}");
-    log::info!("lcd_driver_init OK");
+    // Panel profile picked from the panel's ID (SH8601 / CO5300).
+    let mut lcd = display::Display::init(None).expect("display init failed");
+    if let Err(e) = lcd.set_te_sync(LCD_TE_GPIO) {
+        log::warn!("TE sync unavailable: {e}");
+    }
+    log::info!("display init OK");
+
+    match stored {
+        Some(t) => {
+            log::info!("touch transform loaded from NVS");
+            touch::set_transform(t);
+        }
+        None => {
+            log::info!("no stored touch calibration, using the panel defaults");
+            let (gap_x, gap_y) = lcd.profile().gap;
+            touch::set_transform(
+                TouchTransform::IDENTITY
+                    .with_gap(gap_x as i16, gap_y as i16)
+                    .with_orientation(TOUCH_ROTATION, TOUCH_MIRROR_X, TOUCH_MIRROR_Y),
+            );
+        }
+    }
 
     unsafe {
         // ── 2. LVGL init ──────────────────────────────────────────────────────
         lvgl_sys::lv_init();
 
-        // ── 3. Two DMA-capable pixel buffers for double-buffering ─────────────
-        // Must be internal SRAM: esp-lcd SPI driver calls esp_ptr_dma_capable()
-        // which rejects PSRAM. Two 100-row buffers (~182KB total).
-        let pixel_size = core::mem::size_of::<lvgl_sys::lv_color_t>();
-        let buf1 = esp_idf_svc::sys::heap_caps_malloc(
-            DRAW_BUF_PIXELS * pixel_size,
-            esp_idf_svc::sys::MALLOC_CAP_DMA,
-        ) as *mut lvgl_sys::lv_color_t;
-        let buf2 = esp_idf_svc::sys::heap_caps_malloc(
-            DRAW_BUF_PIXELS * pixel_size,
-            esp_idf_svc::sys::MALLOC_CAP_DMA,
-        ) as *mut lvgl_sys::lv_color_t;
-        assert!(!buf1.is_null() && !buf2.is_null(), "LVGL draw buf alloc failed");
-
-        // ── 4. Draw buffer struct (leaked: LVGL holds a pointer to it) ────────
-        let disp_buf: &'static mut lvgl_sys::lv_disp_draw_buf_t =
-            Box::leak(Box::new(core::mem::zeroed()));
-        lvgl_sys::lv_disp_draw_buf_init(
-            disp_buf,
-            buf1 as *mut _,
-            buf2 as *mut _,
-            DRAW_BUF_PIXELS as u32,
-        );
-
-        // ── 5. Display driver (leaked: LVGL 8.x stores the pointer, not a copy) ─
-        let disp_drv: &'static mut lvgl_sys::lv_disp_drv_t =
-            Box::leak(Box::new(core::mem::zeroed()));
-        lvgl_sys::lv_disp_drv_init(disp_drv);
-        disp_drv.hor_res = LCD_W as lvgl_sys::lv_coord_t;
-        disp_drv.ver_res = LCD_H as lvgl_sys::lv_coord_t;
-        disp_drv.draw_buf = disp_buf;
-        disp_drv.flush_cb = Some(lvgl_flush_cb);
-        lvgl_sys::lv_disp_drv_register(disp_drv);
-        log::info!("LVGL display registered");
+        // ── 3. Draw buffers + display driver ─────────────────────────────────
+        // Full-frame PSRAM framebuffer when PSRAM is available, otherwise two
+        // 100-row internal DMA buffers (~182KB of internal SRAM).
+        display::register_lvgl(lcd, display::DisplayMode::auto()).expect("LVGL display registration failed");
 
-        // ── 6. Input device (touch) ───────────────────────────────────────────
+        // ── 4. Input device (touch) ───────────────────────────────────────────
         let indev_drv: &'static mut lvgl_sys::lv_indev_drv_t =
             Box::leak(Box::new(core::mem::zeroed()));
         lvgl_sys::lv_indev_drv_init(indev_drv);
@@ -235,141 +165,43 @@ fn main() {
         lvgl_sys::lv_indev_drv_register(indev_drv);
         log::info!("LVGL touch input registered");
 
-        // ── 7. Two-screen UI ──────────────────────────────────────────────────
-        // Screen 1: the default screen LVGL created when the display was registered.
-        SCREEN1 = lvgl_sys::lv_disp_get_scr_act(lvgl_sys::lv_disp_get_default());
-
-        // ── Screen 1: Spaceship bridge scene ─────────────────────────────────────
-
-        // Black base background for the screen
-        lvgl_sys::lv_obj_set_style_bg_color(
-            SCREEN1,
-            lvgl_sys::_LV_COLOR_MAKE(0x1a, 0x20, 0x40),
-            lvgl_sys::LV_STATE_DEFAULT,
-        );
-
-        // ── Background image (466×466) ────────────────────────────────────────────
-        let bg_dsc = Box::leak(Box::new(make_dsc(
-            &spaceship::BG_FRAME,
-            466,
-            466,
-        )));
-        BG_DSC = bg_dsc as *const _;
-        let bg_img = lvgl_sys::lv_img_create(SCREEN1);
-        lvgl_sys::lv_img_set_src(bg_img, bg_dsc as *mut lvgl_sys::lv_img_dsc_t as *const _);
-        lvgl_sys::lv_obj_set_pos(bg_img, 0, 0);
-
-        // ── Crew descriptors (shared frames for all 3 crew) ───────────────────────
-        let crew_a_dsc = Box::leak(Box::new(make_dsc(
-            &spaceship::CREW_FRAME_A,
-            spaceship::CREW_W as u32,
-            spaceship::CREW_H as u32,
-        )));
-        let crew_b_dsc = Box::leak(Box::new(make_dsc(
-            &spaceship::CREW_FRAME_B,
-            spaceship::CREW_W as u32,
-            spaceship::CREW_H as u32,
-        )));
-        CREW_DSC_A = crew_a_dsc as *const _;
-        CREW_DSC_B = crew_b_dsc as *const _;
-
-        // ── Crew widgets: (x, y) top-left of sprite ───────────────────────────────
-        // Positions: center sprite over these display coords.
-        // Crew #1 (left):        center at ( 80, 100)
-        // Crew #3 (back-center): center at (210,  80)
-        // Crew #2 (right):       center at (340, 100)
-        let crew_positions: [(i16, i16); 3] = [
-            (( 80 - spaceship::CREW_W / 2) as i16, (100 - spaceship::CREW_H / 2) as i16), // crew #1 left
-            ((210 - spaceship::CREW_W / 2) as i16, ( 80 - spaceship::CREW_H / 2) as i16), // crew #3 back-center
-            ((340 - spaceship::CREW_W / 2) as i16, (100 - spaceship::CREW_H / 2) as i16), // crew #2 right
-        ];
-        for i in 0..3 {
-            let w = lvgl_sys::lv_img_create(SCREEN1);
-            lvgl_sys::lv_img_set_src(w, crew_a_dsc as *mut lvgl_sys::lv_img_dsc_t as *const _);
-            lvgl_sys::lv_obj_set_pos(w, crew_positions[i].0, crew_positions[i].1);
-            CREW_WIDGETS[i] = w;
-        }
-
-        // ── Commander descriptor and widget ──────────────────────────────────────
-        let cmd_a_dsc = Box::leak(Box::new(make_dsc(&spaceship::CMD_FRAME_A, spaceship::CMD_W as u32, spaceship::CMD_H as u32)));
-        let cmd_b_dsc = Box::leak(Box::new(make_dsc(&spaceship::CMD_FRAME_B, spaceship::CMD_W as u32, spaceship::CMD_H as u32)));
-        let cmd_c_dsc = Box::leak(Box::new(make_dsc(&spaceship::CMD_FRAME_C, spaceship::CMD_W as u32, spaceship::CMD_H as u32)));
-        CMD_DSC_A = cmd_a_dsc as *const _;
-        CMD_DSC_B = cmd_b_dsc as *const _;
-        CMD_DSC_C = cmd_c_dsc as *const _;
-
-        // Commander center at (205, 340); sprite top-left:
-        let cmd_widget = lvgl_sys::lv_img_create(SCREEN1);
-        lvgl_sys::lv_img_set_src(cmd_widget, cmd_a_dsc as *mut lvgl_sys::lv_img_dsc_t as *const _);
-        lvgl_sys::lv_obj_set_pos(cmd_widget,
-            (205 - spaceship::CMD_W / 2) as i16,
-            (340 - spaceship::CMD_H / 2) as i16,
-        );
-        CMD_WIDGET = cmd_widget;
-
-        // ── Console blink widget ──────────────────────────────────────────────────
-        let blink_a_dsc = Box::leak(Box::new(make_dsc(&spaceship::BLINK_FRAME_A, spaceship::BLINK_W as u32, spaceship::BLINK_H as u32)));
-        let blink_b_dsc = Box::leak(Box::new(make_dsc(&spaceship::BLINK_FRAME_B, spaceship::BLINK_W as u32, spaceship::BLINK_H as u32)));
-        BLINK_DSC_A = blink_a_dsc as *const _;
-        BLINK_DSC_B = blink_b_dsc as *const _;
-
-        // Position inside the back-center console screen area (x:183..283, y:30..95)
-        // Blink widget at (193, 35) — 20×10 overlay
-        let blink_widget = lvgl_sys::lv_img_create(SCREEN1);
-        lvgl_sys::lv_img_set_src(blink_widget, blink_a_dsc as *mut lvgl_sys::lv_img_dsc_t as *const _);
-        lvgl_sys::lv_obj_set_pos(blink_widget, 193, 35);
-        BLINK_WIDGET = blink_widget;
-
-        // ── Animation timers ──────────────────────────────────────────────────────
-        lvgl_sys::lv_timer_create(Some(crew_timer_cb),  600,  core::ptr::null_mut());
-        lvgl_sys::lv_timer_create(Some(cmd_timer_cb),   800,  core::ptr::null_mut());
-        lvgl_sys::lv_timer_create(Some(blink_timer_cb), 1200, core::ptr::null_mut());
-
-        // Screen 2: new screen object (parent = null → creates a standalone screen)
-        SCREEN2 = lvgl_sys::lv_obj_create(core::ptr::null_mut());
-
-        // Blue background for screen 2 so it's visually distinct
-        lvgl_sys::lv_obj_set_style_bg_color(
-            SCREEN2,
-            lvgl_sys::_LV_COLOR_MAKE(0x00, 0x30, 0x80),
-            lvgl_sys::LV_STATE_DEFAULT,
-        );
-
-        let label2 = lvgl_sys::lv_label_create(SCREEN2);
-        lvgl_sys::lv_label_set_text(label2, b"Screen 2\0".as_ptr() as *const i8);
-        lvgl_sys::lv_obj_align(label2, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
-
-        // Attach gesture callbacks — LVGL sends LV_EVENT_GESTURE to the screen
-        // when a drag exceeds LV_INDEV_DEF_GESTURE_LIMIT (default 50px).
-        lvgl_sys::lv_obj_add_event_cb(
-            SCREEN1,
-            Some(gesture_cb),
-            lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE,
-            core::ptr::null_mut(),
-        );
-        lvgl_sys::lv_obj_add_event_cb(
-            SCREEN2,
-            Some(gesture_cb),
-            lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE,
-            core::ptr::null_mut(),
-        );
-
-        log::info!("Two screens created, gesture callbacks attached");
+        // ── 5. Screens ────────────────────────────────────────────────────────
+        // Swipe left/right between them, in this order; the next page follows
+        // the finger. Built on first entry, torn down after SCREEN_TEARDOWN
+        // unvisited.
+        screens::ScreenManager::new()
+            .with_wrap_around(true)
+            .with_idle_teardown(Some(SCREEN_TEARDOWN))
+            .with_transition(
+                Transition::new(TransitionKind::Move)
+                    .duration(200)
+                    .easing(Easing::EaseOut),
+            )
+            .with_interactive_drag(true)
+            .add(screens::bridge::BridgeScreen::default())
+            .add(screens::info::InfoScreen)
+            .install();
     }
 
-    // ── 8. LVGL timer loop ────────────────────────────────────────────────────
+    // ── 6. LVGL timer loop ────────────────────────────────────────────────────
     log::info!("Entering LVGL loop");
+    let mut auto_dim = auto_dim::AutoDim::new(auto_dim::AutoDimConfig::default());
+    let mut next_sleep = Instant::now();
     loop {
-        // Poll touch BEFORE lv_timer_handler() so the indev callback
-        // (called inside lv_timer_handler) sees the current state.
-        match ft3168.read_touch() {
-            Ok(Some((x, y))) => {
-                TOUCH_X.store(x as i32, Ordering::Relaxed);
-                TOUCH_Y.store(y as i32, Ordering::Relaxed);
-                TOUCH_PRESSED.store(true, Ordering::Relaxed);
+        if let Some(g) = touch::take_gesture() {
+            on_hw_gesture(g);
+        }
+        auto_dim.set_full(settings::brightness());
+        if let Some(level) = auto_dim.update(touch::idle_time()) {
+            match display::with_display(|d| d.set_brightness(level)) {
+                Ok(()) => auto_dim.commit(level),
+                Err(e) => log::warn!("set brightness failed: {e}"),
             }
-            _ => {
-                TOUCH_PRESSED.store(false, Ordering::Relaxed);
+        }
+        if touch::idle_time() >= IDLE_SLEEP && Instant::now() >= next_sleep {
+            if let Err(e) = touch::light_sleep_until_touch() {
+                log::warn!("light sleep failed: {e}");
+                next_sleep = Instant::now() + SLEEP_RETRY;
             }
         }
 
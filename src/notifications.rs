@@ -0,0 +1,23 @@
+// src/notifications.rs
+// Notifications shown in the notifications overlay, newest first. Nothing
+// posts to the list yet; the overlay reads and clears it.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+static LIST: Mutex<VecDeque<Notification>> = Mutex::new(VecDeque::new());
+
+/// Current notifications, newest first.
+pub fn snapshot() -> Vec<Notification> {
+    LIST.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear() {
+    LIST.lock().unwrap().clear();
+}
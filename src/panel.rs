@@ -0,0 +1,125 @@
+// src/panel.rs
+// AMOLED panel profiles: init table, RAM offset, ID and brightness limits.
+// The C lcd_driver only knows how to drive an SH8601-compatible QSPI panel;
+// everything that differs between panel ICs lives here.
+
+use std::sync::Mutex;
+
+/// One panel init command: DCS command, parameters, delay after sending.
+#[derive(Debug, Clone, Copy)]
+pub struct InitCmd {
+    pub cmd: u8,
+    pub data: &'static [u8],
+    pub delay_ms: u32,
+}
+
+const fn cmd(cmd: u8, data: &'static [u8], delay_ms: u32) -> InitCmd {
+    InitCmd { cmd, data, delay_ms }
+}
+
+#[derive(Debug)]
+pub struct PanelProfile {
+    pub name: &'static str,
+    /// RDID1 (0xDA) value used for auto-detection; `None` never matches.
+    pub id: Option<u8>,
+    pub init_cmds: &'static [InitCmd],
+    /// Offset of the visible area in panel RAM, `(x, y)`.
+    pub gap: (i32, i32),
+    /// Brightness register value mapped to `set_brightness(255)`.
+    pub max_brightness: u8,
+    /// High-brightness-mode level written to register 0x63 after init;
+    /// `None` if the panel has no HBM.
+    pub hbm: Option<u8>,
+}
+
+/// Panel ICs shipped on the board so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelKind {
+    Sh8601,
+    /// Most 2024+ hardware.
+    Co5300,
+}
+
+impl PanelKind {
+    pub fn profile(self) -> &'static PanelProfile {
+        match self {
+            PanelKind::Sh8601 => &SH8601,
+            PanelKind::Co5300 => &CO5300,
+        }
+    }
+}
+
+static SH8601: PanelProfile = PanelProfile {
+    name: "SH8601",
+    id: Some(0x86),
+    init_cmds: &[
+        cmd(0x11, &[], 120),          // Sleep Out
+        cmd(0x44, &[0x01, 0xD1], 0),  // Set tear scanline
+        cmd(0x35, &[0x00], 0),        // TE On
+        cmd(0x53, &[0x20], 10),       // Write CTRL Display
+        cmd(0x51, &[0x00], 10),       // Brightness = 0
+        cmd(0x29, &[], 10),           // Display On
+        cmd(0x51, &[0xFF], 0),        // Brightness = max
+    ],
+    gap: (0, 0),
+    max_brightness: 0xFF,
+    hbm: None,
+};
+
+static CO5300: PanelProfile = PanelProfile {
+    name: "CO5300",
+    // RDID1 on CO5300 boards varies; it is the fallback instead.
+    id: None,
+    init_cmds: &[
+        cmd(0x11, &[], 80),     // Sleep Out
+        cmd(0xC4, &[0x80], 0),  // Enable QSPI interface
+        cmd(0x35, &[0x00], 0),  // TE On (V-blank only)
+        cmd(0x53, &[0x20], 1),  // Write CTRL Display
+        cmd(0x51, &[0x00], 1),  // Brightness = 0
+        cmd(0x29, &[], 10),     // Display On
+        cmd(0x51, &[0xFF], 0),  // Brightness = max
+    ],
+    gap: (6, 0),
+    max_brightness: 0xFF,
+    hbm: Some(0xFF),
+};
+
+/// Profile used when no registered profile matches the panel ID.
+pub const FALLBACK: PanelKind = PanelKind::Co5300;
+
+// Profiles tried by `detect`, in order. Board-specific panels go in front
+// of the built-in ones via `register`.
+static REGISTRY: Mutex<Vec<&'static PanelProfile>> = Mutex::new(Vec::new());
+
+/// Add a panel profile for auto-detection. Registered profiles take
+/// precedence over the built-in ones, so a board revision can override an ID.
+/// Only `Display::init(None)` consults the registry, so register before it:
+///
+/// ```ignore
+/// static RM690B0: PanelProfile = PanelProfile {
+///     name: "RM690B0",
+///     id: Some(0x69),
+///     init_cmds: &[/* ... */],
+///     gap: (0, 0),
+///     max_brightness: 0xFF,
+///     hbm: None,
+/// };
+/// panel::register(&RM690B0);
+/// let lcd = Display::init(None)?;
+/// ```
+#[allow(dead_code)]
+pub fn register(profile: &'static PanelProfile) {
+    REGISTRY.lock().unwrap().push(profile);
+}
+
+/// The profile for a panel reporting `id`.
+pub fn detect(id: u8) -> &'static PanelProfile {
+    let registered = REGISTRY.lock().unwrap();
+    registered
+        .iter()
+        .rev()
+        .copied()
+        .chain([PanelKind::Sh8601.profile(), PanelKind::Co5300.profile()])
+        .find(|p| p.id == Some(id))
+        .unwrap_or_else(|| FALLBACK.profile())
+}
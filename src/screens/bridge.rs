@@ -0,0 +1,171 @@
+// src/screens/bridge.rs
+// Spaceship bridge scene: background, three crew members, the commander and
+// a blinking console. The characters are `AnimatedSprite`s stepped by one
+// scheduler, which runs while the screen is visible; the story in
+// `scenes/bridge.scene` directs them and says how they react to taps.
+// Everything lives in a `Scene` that exists only while the object tree does.
+
+use super::Screen;
+use crate::anim::{self, AnimatedSprite, Clip, FrameSet, LoopMode, Scheduler, Timing};
+use crate::spaceship;
+use crate::timeline::{Actor, Player, Timeline};
+
+/// The story the scene plays; see the file for the format.
+const SCENE: &str = include_str!("../../scenes/bridge.scene");
+
+// Frame durations.
+const CMD_FRAME_MS: u32 = 800;
+const BLINK_FRAME_MS: u32 = 1200;
+const ALERT_FRAME_MS: u32 = 300;
+
+/// Crew frames last up to this much longer, at random, so the crew never
+/// settles into a visible rhythm.
+const CREW_JITTER_MS: u32 = 150;
+
+/// Everything `create` builds.
+struct Scene {
+    // Dropped first: deletes the timer before the sprites go.
+    scheduler: Scheduler,
+    // Boxed: the background widget points at it.
+    bg: Box<lvgl_sys::lv_img_dsc_t>,
+}
+
+impl Drop for Scene {
+    fn drop(&mut self) {
+        unsafe { lvgl_sys::lv_img_cache_invalidate_src(&*self.bg as *const _ as *const _) };
+    }
+}
+
+/// A clip holding a single `w`×`h` frame.
+fn pose(w: i32, h: i32, pixels: &'static [u16]) -> Clip {
+    Clip::new(&FrameSet::new(w as u32, h as u32, &[pixels]), &[1], LoopMode::Once)
+}
+
+#[derive(Default)]
+pub struct BridgeScreen {
+    scene: Option<Scene>,
+}
+
+impl Screen for BridgeScreen {
+    fn create(&mut self, root: *mut lvgl_sys::lv_obj_t) {
+        let bg = Box::new(anim::image_dsc(&spaceship::BG_FRAME, 466, 466));
+        let mut scheduler = Scheduler::new();
+
+        unsafe {
+            // Black base background for the screen
+            lvgl_sys::lv_obj_set_style_bg_color(
+                root,
+                lvgl_sys::_LV_COLOR_MAKE(0x1a, 0x20, 0x40),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+
+            // ── Background image (466×466) ────────────────────────────────────────────
+            let bg_img = lvgl_sys::lv_img_create(root);
+            lvgl_sys::lv_img_set_src(bg_img, &*bg as *const _ as *const _);
+            lvgl_sys::lv_obj_set_pos(bg_img, 0, 0);
+        }
+
+        // ── Crew: (x, y) top-left of sprite, frame ms, phase ms ──────────────────
+        // All 3 crew share one frame set but keep their own pace.
+        // Positions: center sprite over these display coords.
+        // Crew #1 (left):        center at ( 80, 100)
+        // Crew #3 (back-center): center at (210,  80)
+        // Crew #2 (right):       center at (340, 100)
+        let crew_frames = FrameSet::new(
+            spaceship::CREW_W as u32,
+            spaceship::CREW_H as u32,
+            &[&spaceship::CREW_FRAME_A, &spaceship::CREW_FRAME_B],
+        );
+        let crew_turn = pose(spaceship::CREW_W, spaceship::CREW_H, &spaceship::CREW_FRAME_TURN);
+        let crew: [(&str, i32, i32, u32, u32); 3] = [
+            ("crew1",  80 - spaceship::CREW_W / 2, 100 - spaceship::CREW_H / 2, 500,   0), // left
+            ("crew3", 210 - spaceship::CREW_W / 2,  80 - spaceship::CREW_H / 2, 600, 250), // back-center
+            ("crew2", 340 - spaceship::CREW_W / 2, 100 - spaceship::CREW_H / 2, 700, 900), // right
+        ];
+        let mut cast = Vec::new();
+        for (name, x, y, frame_ms, phase_ms) in crew {
+            let idle = Clip::new(&crew_frames, &[frame_ms], LoopMode::Loop);
+            let mut sprite = AnimatedSprite::new(root, &idle)
+                .with_timing(Timing { phase_ms, jitter_ms: CREW_JITTER_MS });
+            sprite.set_pos(x, y);
+            let sprite = scheduler.add(sprite);
+            cast.push(Actor { name, sprite, clips: vec![("idle", idle), ("turn", crew_turn.clone())] });
+        }
+
+        // ── Commander: A→B→C→A ──────────────────────────────────────────────────
+        // Commander center at (205, 340); sprite top-left:
+        let cmd_frames = FrameSet::new(
+            spaceship::CMD_W as u32,
+            spaceship::CMD_H as u32,
+            &[&spaceship::CMD_FRAME_A, &spaceship::CMD_FRAME_B, &spaceship::CMD_FRAME_C],
+        );
+        let cmd_idle = Clip::new(&cmd_frames, &[CMD_FRAME_MS], LoopMode::Loop);
+        let mut cmd = AnimatedSprite::new(root, &cmd_idle);
+        cmd.set_pos(205 - spaceship::CMD_W / 2, 340 - spaceship::CMD_H / 2);
+        cast.push(Actor {
+            name: "commander",
+            sprite: scheduler.add(cmd),
+            clips: vec![
+                ("idle", cmd_idle),
+                ("point", pose(spaceship::CMD_W, spaceship::CMD_H, &spaceship::CMD_FRAME_POINT)),
+            ],
+        });
+
+        // ── Console blink: toggles between two colors ─────────────────────────────
+        // Position inside the back-center console screen area (x:183..283, y:30..95)
+        // Blink widget at (193, 35) — 20×10 overlay
+        let blink_frames = FrameSet::new(
+            spaceship::BLINK_W as u32,
+            spaceship::BLINK_H as u32,
+            &[&spaceship::BLINK_FRAME_A, &spaceship::BLINK_FRAME_B],
+        );
+        let alert_frames = FrameSet::new(
+            spaceship::BLINK_W as u32,
+            spaceship::BLINK_H as u32,
+            &[&spaceship::BLINK_ALERT_A, &spaceship::BLINK_ALERT_B],
+        );
+        let blink_idle = Clip::new(&blink_frames, &[BLINK_FRAME_MS], LoopMode::Loop);
+        let mut blink = AnimatedSprite::new(root, &blink_idle);
+        blink.set_pos(193, 35);
+        cast.push(Actor {
+            name: "blink",
+            sprite: scheduler.add(blink),
+            clips: vec![
+                ("idle", blink_idle),
+                ("alert", Clip::new(&alert_frames, &[ALERT_FRAME_MS], LoopMode::Loop)),
+            ],
+        });
+
+        // ── Story ─────────────────────────────────────────────────────────────────
+        match Timeline::parse(SCENE).and_then(|t| Player::new(t, &cast)) {
+            Ok(mut player) => {
+                for id in player.tappable() {
+                    scheduler.set_tappable(id, spaceship::SPRITE_BG);
+                }
+                scheduler.set_director(move |dt, stage| player.advance(dt, stage));
+            }
+            Err(e) => log::warn!("bridge scene not played: {e}"),
+        }
+
+        // Paused until the screen is entered.
+        self.scene = Some(Scene { scheduler, bg });
+    }
+
+    fn on_enter(&mut self) {
+        if let Some(scene) = &mut self.scene {
+            scene.scheduler.resume();
+        }
+    }
+
+    fn on_leave(&mut self) {
+        if let Some(scene) = &mut self.scene {
+            scene.scheduler.pause();
+        }
+    }
+
+    fn destroy(&mut self) {
+        // Deletes the timer, the sprite widgets and the frame sets; the
+        // rest of the tree goes with the root.
+        self.scene = None;
+    }
+}
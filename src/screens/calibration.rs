@@ -0,0 +1,177 @@
+// src/screens/calibration.rs
+// On-screen touch calibration, pushed from the info screen: three targets,
+// one tap each. The affine calibration is solved from where the taps landed
+// and saved to NVS, then the screen pops itself. Leaving it early (a back
+// swipe) keeps the previous calibration.
+
+use super::Screen;
+use crate::touch;
+use crate::touch_transform::{self, Affine, TouchTransform};
+
+// Target centres: a wide triangle well inside the visible circle.
+const TARGETS: [(i16, i16); 3] = [(233, 90), (110, 330), (356, 330)];
+const TARGET_SIZE: i16 = 24;
+const POLL_MS: u32 = 20;
+
+pub struct CalibrationScreen {
+    target: *mut lvgl_sys::lv_obj_t,
+    /// Non-null while a run is in progress.
+    timer: *mut lvgl_sys::lv_timer_t,
+    // Transform in force before the run, restored unless it succeeds.
+    previous: TouchTransform,
+    // `previous` with an identity calibration: samples are taken in this space.
+    base: TouchTransform,
+    // Ignore the touch that started the run until it is released.
+    armed: bool,
+    last_point: Option<(f32, f32)>,
+    samples: [(f32, f32); 3],
+    step: usize,
+}
+
+impl Default for CalibrationScreen {
+    fn default() -> Self {
+        Self {
+            target: core::ptr::null_mut(),
+            timer: core::ptr::null_mut(),
+            previous: TouchTransform::IDENTITY,
+            base: TouchTransform::IDENTITY,
+            armed: false,
+            last_point: None,
+            samples: [(0.0, 0.0); 3],
+            step: 0,
+        }
+    }
+}
+
+impl Screen for CalibrationScreen {
+    fn create(&mut self, root: *mut lvgl_sys::lv_obj_t) {
+        unsafe {
+            lvgl_sys::lv_obj_set_style_bg_color(
+                root,
+                lvgl_sys::_LV_COLOR_MAKE(0x00, 0x00, 0x00),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_clear_flag(root, lvgl_sys::LV_OBJ_FLAG_SCROLLABLE);
+
+            let label = lvgl_sys::lv_label_create(root);
+            lvgl_sys::lv_label_set_text(label, b"Tap each target\0".as_ptr() as *const i8);
+            lvgl_sys::lv_obj_set_style_text_color(
+                label,
+                lvgl_sys::_LV_COLOR_MAKE(0xFF, 0xFF, 0xFF),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_align(label, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
+
+            self.target = lvgl_sys::lv_obj_create(root);
+            lvgl_sys::lv_obj_set_size(self.target, TARGET_SIZE, TARGET_SIZE);
+            lvgl_sys::lv_obj_set_style_radius(
+                self.target,
+                lvgl_sys::LV_RADIUS_CIRCLE as lvgl_sys::lv_coord_t,
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_set_style_bg_color(
+                self.target,
+                lvgl_sys::_LV_COLOR_MAKE(0xFF, 0x22, 0x00),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_clear_flag(self.target, lvgl_sys::LV_OBJ_FLAG_CLICKABLE);
+        }
+    }
+
+    /// Start a run from the first target.
+    fn on_enter(&mut self) {
+        self.previous = touch::transform();
+        self.base = TouchTransform { calibration: Affine::IDENTITY, ..self.previous };
+        touch::set_transform(self.base);
+        self.armed = !touch::state().pressed;
+        self.last_point = None;
+        self.step = 0;
+        unsafe {
+            place_target(self.target, 0);
+            // The screen is boxed by the manager, so its address is stable
+            // until `destroy`, which deletes the timer first.
+            self.timer = lvgl_sys::lv_timer_create(Some(poll_cb), POLL_MS, self as *mut Self as *mut _);
+        }
+        log::info!("touch calibration started");
+    }
+
+    fn on_leave(&mut self) {
+        if self.stop() {
+            touch::set_transform(self.previous);
+            log::info!("touch calibration abandoned");
+        }
+    }
+
+    fn destroy(&mut self) {
+        self.on_leave();
+        self.target = core::ptr::null_mut();
+    }
+}
+
+impl CalibrationScreen {
+    /// Delete the poll timer; `false` if no run was in progress.
+    fn stop(&mut self) -> bool {
+        if self.timer.is_null() {
+            return false;
+        }
+        unsafe { lvgl_sys::lv_timer_del(self.timer) };
+        self.timer = core::ptr::null_mut();
+        true
+    }
+
+    /// Samples the touch state; a tap is recorded where the finger lifted.
+    fn poll(&mut self) {
+        let state = touch::state();
+        if state.pressed {
+            if self.armed {
+                self.last_point = Some((state.x as f32, state.y as f32));
+            }
+            return;
+        }
+        if !self.armed {
+            self.armed = true;
+            return;
+        }
+        let Some(point) = self.last_point.take() else {
+            return;
+        };
+
+        self.samples[self.step] = point;
+        self.step += 1;
+        if self.step < TARGETS.len() {
+            unsafe { place_target(self.target, self.step) };
+            return;
+        }
+        self.stop();
+        self.finish();
+        super::pop();
+    }
+
+    fn finish(&self) {
+        let targets = TARGETS.map(|(x, y)| (x as f32, y as f32));
+        match Affine::from_points(self.samples, targets) {
+            Some(calibration) => {
+                let t = TouchTransform { calibration, ..self.base };
+                touch::set_transform(t);
+                match touch_transform::save(&t) {
+                    Ok(()) => log::info!("touch calibration saved: {calibration:?}"),
+                    Err(e) => log::warn!("touch calibration not saved: {e}"),
+                }
+            }
+            None => {
+                log::warn!("touch calibration failed: samples are collinear");
+                touch::set_transform(self.previous);
+            }
+        }
+    }
+}
+
+unsafe fn place_target(target: *mut lvgl_sys::lv_obj_t, step: usize) {
+    let (cx, cy) = TARGETS[step];
+    lvgl_sys::lv_obj_set_pos(target, cx - TARGET_SIZE / 2, cy - TARGET_SIZE / 2);
+}
+
+unsafe extern "C" fn poll_cb(timer: *mut lvgl_sys::lv_timer_t) {
+    let screen = &mut *((*timer).user_data as *mut CalibrationScreen);
+    screen.poll();
+}
@@ -0,0 +1,143 @@
+// src/screens/indicator.rs
+// Page indicator: one dot per screen along an arc near the bottom of the
+// circle, on lv_layer_top so it stays put while screens slide. The active
+// page is marked by a larger dot that travels along the arc with the slide.
+
+use std::f32::consts::FRAC_PI_2;
+
+use super::transition::Easing;
+use crate::{anim, safe_area};
+
+/// Distance of the dot centres from the circle's edge.
+const INSET: i32 = 22;
+/// Arc length between neighbouring dot centres.
+const SPACING: f32 = 18.0;
+const DOT: i32 = 8;
+const ACTIVE_DOT: i32 = 12;
+
+pub struct PageIndicator {
+    dots: Vec<*mut lvgl_sys::lv_obj_t>,
+    active: *mut lvgl_sys::lv_obj_t,
+    page: usize,
+    /// Where the active marker was last put, in milliradians.
+    pos: i32,
+}
+
+/// Angle of page `i` of `n`, in milliradians. Pages run left to right, so
+/// page 0 has the largest angle.
+fn angle_mrad(i: usize, n: usize) -> i32 {
+    let step = SPACING / (safe_area::DISPLAY_R - INSET) as f32;
+    let offset = (n as f32 - 1.0) / 2.0 - i as f32;
+    ((FRAC_PI_2 + offset * step) * 1000.0).round() as i32
+}
+
+/// Centre the `size`×`size` object `obj` on the arc at `mrad`, pulled in if
+/// it would cross the edge of the display.
+unsafe fn place(obj: *mut lvgl_sys::lv_obj_t, size: i32, mrad: i32) {
+    let (cx, cy) = safe_area::arc_point(INSET, mrad as f32 / 1000.0);
+    let (x, y) = safe_area::clamp_rect_to_display(cx - size / 2, cy - size / 2, size, size);
+    lvgl_sys::lv_obj_set_pos(obj, x as lvgl_sys::lv_coord_t, y as lvgl_sys::lv_coord_t);
+}
+
+unsafe fn dot(parent: *mut lvgl_sys::lv_obj_t, size: i32, grey: u8) -> *mut lvgl_sys::lv_obj_t {
+    let d = lvgl_sys::lv_obj_create(parent);
+    lvgl_sys::lv_obj_set_size(d, size as lvgl_sys::lv_coord_t, size as lvgl_sys::lv_coord_t);
+    lvgl_sys::lv_obj_set_style_radius(
+        d,
+        lvgl_sys::LV_RADIUS_CIRCLE as lvgl_sys::lv_coord_t,
+        lvgl_sys::LV_STATE_DEFAULT,
+    );
+    lvgl_sys::lv_obj_set_style_bg_color(
+        d,
+        lvgl_sys::_LV_COLOR_MAKE(grey, grey, grey),
+        lvgl_sys::LV_STATE_DEFAULT,
+    );
+    lvgl_sys::lv_obj_set_style_border_width(d, 0, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_set_style_pad_all(d, 0, lvgl_sys::LV_STATE_DEFAULT);
+    // Touches go to the screen underneath.
+    lvgl_sys::lv_obj_clear_flag(d, lvgl_sys::LV_OBJ_FLAG_CLICKABLE);
+    lvgl_sys::lv_obj_clear_flag(d, lvgl_sys::LV_OBJ_FLAG_SCROLLABLE);
+    d
+}
+
+unsafe extern "C" fn anim_place_active(obj: *mut core::ffi::c_void, mrad: i32) {
+    place(obj as *mut lvgl_sys::lv_obj_t, ACTIVE_DOT, mrad);
+}
+
+impl PageIndicator {
+    /// Dots for `pages` screens, page 0 marked.
+    pub fn new(pages: usize) -> Self {
+        unsafe {
+            let layer = lvgl_sys::lv_disp_get_layer_top(lvgl_sys::lv_disp_get_default());
+            let dots = (0..pages)
+                .map(|i| {
+                    let d = dot(layer, DOT, 0x70);
+                    place(d, DOT, angle_mrad(i, pages));
+                    d
+                })
+                .collect();
+            let active = dot(layer, ACTIVE_DOT, 0xFF);
+            let pos = angle_mrad(0, pages);
+            place(active, ACTIVE_DOT, pos);
+            Self { dots, active, page: 0, pos }
+        }
+    }
+
+    /// Mark `page`. With `slide_ms` > 0 the marker travels along the arc
+    /// over that time with `easing`, in step with the screen transition.
+    pub fn set_page(&mut self, page: usize, slide_ms: u32, easing: Easing) {
+        let (from, to) = (self.pos, angle_mrad(page, self.dots.len()));
+        self.page = page;
+        self.pos = to;
+        unsafe {
+            lvgl_sys::lv_anim_del(self.active as *mut _, Some(anim_place_active));
+            if slide_ms == 0 {
+                place(self.active, ACTIVE_DOT, to);
+                return;
+            }
+            let mut a: lvgl_sys::lv_anim_t = core::mem::zeroed();
+            lvgl_sys::lv_anim_init(&mut a);
+            a.var = self.active as *mut _;
+            a.exec_cb = Some(anim_place_active);
+            a.start_value = from;
+            a.current_value = from;
+            a.end_value = to;
+            a.time = slide_ms;
+            a.path_cb = anim::path_cb(easing);
+            lvgl_sys::lv_anim_start(&a);
+        }
+    }
+
+    /// Put the marker `permille`/1000 of the way from the current page to
+    /// `page`, for a screen that is being dragged in.
+    pub fn track(&mut self, page: usize, permille: i32) {
+        let n = self.dots.len();
+        let (from, to) = (angle_mrad(self.page, n), angle_mrad(page, n));
+        self.pos = from + (to - from) * permille.clamp(0, 1000) / 1000;
+        unsafe {
+            lvgl_sys::lv_anim_del(self.active as *mut _, Some(anim_place_active));
+            place(self.active, ACTIVE_DOT, self.pos);
+        }
+    }
+
+    /// Hide the dots, e.g. while a detail screen is on top.
+    pub fn set_visible(&mut self, visible: bool) {
+        for &obj in self.dots.iter().chain([&self.active]) {
+            unsafe {
+                if visible {
+                    lvgl_sys::lv_obj_clear_flag(obj, lvgl_sys::LV_OBJ_FLAG_HIDDEN);
+                } else {
+                    lvgl_sys::lv_obj_add_flag(obj, lvgl_sys::LV_OBJ_FLAG_HIDDEN);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for PageIndicator {
+    fn drop(&mut self) {
+        for &obj in self.dots.iter().chain([&self.active]) {
+            unsafe { lvgl_sys::lv_obj_del(obj) };
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// src/screens/info.rs
+// Second page: plain blue screen with a label and a button that opens the
+// system details. A long press here opens touch calibration.
+
+use super::calibration::CalibrationScreen;
+use super::system::SystemScreen;
+use super::Screen;
+
+#[derive(Default)]
+pub struct InfoScreen;
+
+impl Screen for InfoScreen {
+    fn create(&mut self, root: *mut lvgl_sys::lv_obj_t) {
+        unsafe {
+            // Blue background so it's visually distinct from the bridge
+            lvgl_sys::lv_obj_set_style_bg_color(
+                root,
+                lvgl_sys::_LV_COLOR_MAKE(0x00, 0x30, 0x80),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+
+            let label = lvgl_sys::lv_label_create(root);
+            lvgl_sys::lv_label_set_text(label, b"Screen 2\0".as_ptr() as *const i8);
+            lvgl_sys::lv_obj_align(label, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
+
+            let btn = lvgl_sys::lv_btn_create(root);
+            lvgl_sys::lv_obj_align(btn, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 60);
+            let btn_label = lvgl_sys::lv_label_create(btn);
+            lvgl_sys::lv_label_set_text(btn_label, b"System\0".as_ptr() as *const i8);
+            lvgl_sys::lv_obj_add_event_cb(
+                btn,
+                Some(system_clicked_cb),
+                lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED,
+                core::ptr::null_mut(),
+            );
+        }
+    }
+
+    fn on_long_press(&mut self) {
+        super::push(CalibrationScreen::default());
+    }
+}
+
+unsafe extern "C" fn system_clicked_cb(_e: *mut lvgl_sys::lv_event_t) {
+    super::push(SystemScreen::default());
+}
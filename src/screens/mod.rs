@@ -0,0 +1,534 @@
+// src/screens/mod.rs
+// Screen manager: an ordered list of screens, swiped through left/right.
+// Each screen builds its own LVGL object tree under a root the manager
+// creates, on first entry, and is told when it becomes (in)visible. Screens
+// not visited for a while are torn down again. Detail screens are pushed
+// on a navigation stack above the list and popped by a swipe in from the
+// left bezel. Edge swipes up/down open overlay panels on top of the current
+// screen. Dots along the bottom edge show the current page. Screen changes
+// play a configurable transition, which follows the finger while swiping
+// between pages. LVGL thread only.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use crate::safe_area;
+
+pub mod bridge;
+pub mod calibration;
+pub mod indicator;
+pub mod info;
+pub mod overlay;
+pub mod system;
+pub mod transition;
+
+use indicator::PageIndicator;
+use overlay::{Overlay, OverlayKind};
+use transition::Transition;
+
+/// How often the manager looks for screens to tear down.
+const RECLAIM_PERIOD_MS: u32 = 1000;
+
+/// Width of the left bezel region a back swipe must start in.
+const BEZEL_PX: i32 = 40;
+
+/// Interactive swipes: how often the finger is followed, how far it must
+/// move sideways before the next screen starts to follow, and how far
+/// (of `transition::DONE`) it must have come for a release to complete it.
+const DRAG_POLL_MS: u32 = 10;
+const DRAG_SLOP: i32 = 12;
+const DRAG_COMMIT: i32 = 300;
+
+/// A full-screen page. All hooks run on the LVGL thread.
+pub trait Screen {
+    /// Build the object tree under `root`, an otherwise empty LVGL screen.
+    /// Called on first entry, and again after a teardown.
+    fn create(&mut self, root: *mut lvgl_sys::lv_obj_t);
+    /// The screen is about to become the active one.
+    fn on_enter(&mut self) {}
+    /// The screen is about to be replaced.
+    fn on_leave(&mut self) {}
+    /// `root` and its children are about to be deleted; free everything
+    /// `create` allocated (timers, image descriptors, pointers into the tree).
+    fn destroy(&mut self) {}
+    /// Long press anywhere on this screen.
+    fn on_long_press(&mut self) {}
+    /// Transition used when this screen comes in, and in reverse when it is
+    /// popped. `None` uses the manager's.
+    fn transition(&self) -> Option<Transition> {
+        None
+    }
+}
+
+struct Entry {
+    screen: Box<dyn Screen>,
+    /// Null until the screen is first entered and after a teardown.
+    root: *mut lvgl_sys::lv_obj_t,
+    /// When the screen was last left; `None` while it is the current one.
+    left_at: Option<Instant>,
+}
+
+impl Entry {
+    fn ensure_created(&mut self) {
+        if self.root.is_null() {
+            unsafe {
+                self.root = create_root();
+            }
+            self.screen.create(self.root);
+        }
+    }
+
+    fn new(screen: Box<dyn Screen>) -> Self {
+        Self { screen, root: core::ptr::null_mut(), left_at: None }
+    }
+
+    fn teardown(&mut self) {
+        if !self.root.is_null() {
+            self.screen.destroy();
+            unsafe { lvgl_sys::lv_obj_del(self.root) };
+            self.root = core::ptr::null_mut();
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ScreenManager {
+    entries: Vec<Entry>,
+    current: usize,
+    wrap_around: bool,
+    teardown_after: Option<Duration>,
+    overlay: Option<Overlay>,
+    /// Detail screens above `entries[current]`, most recent last.
+    stack: Vec<Entry>,
+    /// Popped screens, torn down once their slide-out has finished.
+    retired: Vec<Entry>,
+    /// Only shown with more than one screen.
+    indicator: Option<PageIndicator>,
+    transition: Transition,
+    interactive: bool,
+    /// Page being dragged in.
+    drag: Option<Drag>,
+    was_pressed: bool,
+    /// A drag happened during the current contact: the swipe gesture that
+    /// follows it has been dealt with.
+    dragged: bool,
+}
+
+struct Drag {
+    target: usize,
+    forward: bool,
+}
+
+thread_local! {
+    static MANAGER: RefCell<Option<ScreenManager>> = const { RefCell::new(None) };
+}
+
+impl ScreenManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Swiping past the last screen continues at the first, and vice versa.
+    pub fn with_wrap_around(mut self, wrap: bool) -> Self {
+        self.wrap_around = wrap;
+        self
+    }
+
+    /// Tear down screens that have not been visible for `after`. `None`
+    /// (the default) keeps every screen alive once created.
+    pub fn with_idle_teardown(mut self, after: Option<Duration>) -> Self {
+        self.teardown_after = after;
+        self
+    }
+
+    /// Transition for screens that do not pick their own.
+    /// Default: `Transition::default()`.
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Let the next or previous page follow the finger during a sideways
+    /// swipe, instead of sliding in once the swipe is recognised.
+    pub fn with_interactive_drag(mut self, on: bool) -> Self {
+        self.interactive = on;
+        self
+    }
+
+    /// Append a screen; swipe order is registration order.
+    pub fn add(mut self, screen: impl Screen + 'static) -> Self {
+        self.entries.push(Entry::new(Box::new(screen)));
+        self
+    }
+
+    /// Create and show the first screen and make the manager the target of
+    /// swipe navigation. The others are created when first entered.
+    pub fn install(mut self) {
+        assert!(!self.entries.is_empty(), "no screens registered");
+        let first = &mut self.entries[0];
+        first.ensure_created();
+        first.screen.on_enter();
+        unsafe {
+            let initial = lvgl_sys::lv_disp_get_scr_act(lvgl_sys::lv_disp_get_default());
+            lvgl_sys::lv_scr_load(first.root);
+            // The screen LVGL created with the display is not one of ours.
+            lvgl_sys::lv_obj_del(initial);
+            lvgl_sys::lv_timer_create(Some(reclaim_cb), RECLAIM_PERIOD_MS, core::ptr::null_mut());
+            if self.interactive {
+                lvgl_sys::lv_timer_create(Some(drag_cb), DRAG_POLL_MS, core::ptr::null_mut());
+            }
+        }
+        if self.entries.len() > 1 {
+            self.indicator = Some(PageIndicator::new(self.entries.len()));
+        }
+        log::info!("{} screens registered", self.entries.len());
+        MANAGER.with(|m| *m.borrow_mut() = Some(self));
+    }
+
+    fn neighbour(&self, step: isize) -> Option<usize> {
+        let n = self.entries.len() as isize;
+        let i = self.current as isize + step;
+        if (0..n).contains(&i) {
+            Some(i as usize)
+        } else if self.wrap_around && n > 1 {
+            Some(i.rem_euclid(n) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn transition_for(&self, screen: &dyn Screen) -> Transition {
+        screen.transition().unwrap_or(self.transition)
+    }
+
+    /// Make page `index` the current one: lifecycle hooks and the page
+    /// indicator, which moves over `ms` with `t`'s easing. The caller runs
+    /// the transition.
+    fn switch_to(&mut self, index: usize, ms: u32, t: Transition) {
+        let leaving = &mut self.entries[self.current];
+        leaving.screen.on_leave();
+        leaving.left_at = Some(Instant::now());
+        self.current = index;
+        let entry = &mut self.entries[index];
+        entry.ensure_created();
+        entry.left_at = None;
+        entry.screen.on_enter();
+        if let Some(indicator) = &mut self.indicator {
+            indicator.set_page(index, ms, t.easing);
+        }
+    }
+
+    /// Go to page `index`; `forward` = it comes in from the right.
+    fn show(&mut self, index: usize, forward: bool) {
+        if index == self.current {
+            return;
+        }
+        let from = self.entries[self.current].root;
+        let t = self.transition_for(&*self.entries[index].screen);
+        self.switch_to(index, t.duration_ms, t);
+        transition::run(from, self.entries[index].root, forward, t);
+    }
+
+    /// The visible screen: the top of the stack, else the current one.
+    fn top(&mut self) -> &mut Entry {
+        match self.stack.last_mut() {
+            Some(entry) => entry,
+            None => &mut self.entries[self.current],
+        }
+    }
+
+    /// Show `screen` above the visible one, sliding in from the right.
+    fn push(&mut self, screen: Box<dyn Screen>) {
+        if let Some(open) = self.overlay.take() {
+            open.close();
+        }
+        let t = self.transition_for(&*screen);
+        let leaving = self.top();
+        leaving.screen.on_leave();
+        leaving.left_at = Some(Instant::now());
+        let from = leaving.root;
+
+        let mut entry = Entry::new(screen);
+        entry.ensure_created();
+        entry.screen.on_enter();
+        transition::run(from, entry.root, true, t);
+        self.stack.push(entry);
+        if let Some(indicator) = &mut self.indicator {
+            indicator.set_visible(false);
+        }
+        log::debug!("pushed screen, depth {}", self.stack.len());
+    }
+
+    /// Drop the top of the stack and slide back to the screen below.
+    fn pop(&mut self) {
+        let Some(mut popped) = self.stack.pop() else {
+            return;
+        };
+        popped.screen.on_leave();
+        popped.left_at = Some(Instant::now());
+        // The transition it came in with, played backwards.
+        let t = self.transition_for(&*popped.screen);
+        let from = popped.root;
+        self.retired.push(popped);
+
+        let entry = self.top();
+        entry.ensure_created();
+        entry.left_at = None;
+        entry.screen.on_enter();
+        transition::run(from, entry.root, false, t);
+        if self.stack.is_empty() {
+            if let Some(indicator) = &mut self.indicator {
+                indicator.set_visible(true);
+            }
+        }
+        log::debug!("popped screen, depth {}", self.stack.len());
+    }
+
+    fn navigate(&mut self, dir: lvgl_sys::lv_dir_t) {
+        // A page is following the finger; the swipe is its to settle.
+        if self.drag.is_some() {
+            return;
+        }
+        // An open overlay takes every swipe; only the opposite of the one
+        // that opened it does anything.
+        if let Some(open) = &self.overlay {
+            if dir == open.kind.close_dir() {
+                if let Some(open) = self.overlay.take() {
+                    open.close();
+                }
+            }
+            return;
+        }
+        let (start_x, start_y) = crate::touch::press_start();
+        if let Some(kind) = OverlayKind::for_swipe(dir, start_y) {
+            self.overlay = Some(Overlay::open(kind, Some(gesture_cb)));
+            return;
+        }
+        // Inside an app, horizontal swipes only go back, and only from the
+        // left bezel.
+        if !self.stack.is_empty() {
+            if dir == lvgl_sys::LV_DIR_RIGHT as lvgl_sys::lv_dir_t
+                && safe_area::in_left_bezel(start_x, start_y, BEZEL_PX)
+            {
+                self.pop();
+            }
+            return;
+        }
+
+        let step = if dir == lvgl_sys::LV_DIR_LEFT as lvgl_sys::lv_dir_t {
+            1
+        } else if dir == lvgl_sys::LV_DIR_RIGHT as lvgl_sys::lv_dir_t {
+            -1
+        } else {
+            return;
+        };
+        // Already handled by `track_drag`.
+        if self.dragged {
+            return;
+        }
+        if let Some(index) = self.neighbour(step) {
+            self.show(index, step > 0);
+        }
+    }
+
+    /// Follow a sideways drag on a page with the neighbouring page, and
+    /// settle it on release. Runs every `DRAG_POLL_MS`.
+    fn track_drag(&mut self) {
+        let touch = crate::touch::state();
+        let pressed = touch.pressed;
+        if pressed && !self.was_pressed {
+            self.dragged = false;
+        }
+        self.was_pressed = pressed;
+        let (start_x, start_y) = crate::touch::press_start();
+        let dx = touch.x - start_x;
+        let dy = touch.y - start_y;
+
+        let Some(drag) = &self.drag else {
+            let idle = self.stack.is_empty() && self.overlay.is_none() && !transition::is_running();
+            if !pressed || !idle || dx.abs() < DRAG_SLOP || dx.abs() < dy.abs() || !drag_allowed() {
+                return;
+            }
+            // Finger moving left brings in the next page.
+            let step = if dx < 0 { 1 } else { -1 };
+            let Some(target) = self.neighbour(step) else {
+                return;
+            };
+            let entry = &mut self.entries[target];
+            entry.ensure_created();
+            let to = entry.root;
+            let t = self.transition_for(&*self.entries[target].screen);
+            transition::begin(self.entries[self.current].root, to, step > 0, t);
+            self.drag = Some(Drag { target, forward: step > 0 });
+            self.dragged = true;
+            return;
+        };
+
+        let towards = if drag.forward { -dx } else { dx };
+        let progress = ((towards - DRAG_SLOP) * transition::DONE / safe_area::DISPLAY_W)
+            .clamp(0, transition::DONE);
+        if pressed {
+            transition::set_progress(progress);
+            if let Some(indicator) = &mut self.indicator {
+                indicator.track(drag.target, progress);
+            }
+            return;
+        }
+
+        let Drag { target, .. } = *drag;
+        self.drag = None;
+        let t = self.transition_for(&*self.entries[target].screen);
+        let settle = |to: i32| (to - progress).unsigned_abs() * t.duration_ms / transition::DONE as u32;
+        if progress >= DRAG_COMMIT {
+            self.switch_to(target, settle(transition::DONE), t);
+            transition::animate_to(transition::DONE);
+        } else {
+            if let Some(indicator) = &mut self.indicator {
+                indicator.set_page(self.current, settle(0), t.easing);
+            }
+            transition::animate_to(0);
+        }
+    }
+
+    /// Tear down popped screens whose transition is over, and every created
+    /// screen left longer than `teardown_after` ago.
+    fn reclaim(&mut self) {
+        self.retired.retain_mut(|entry| {
+            let done = !transition::involves(entry.root);
+            if done {
+                entry.teardown();
+            }
+            !done
+        });
+
+        let Some(after) = self.teardown_after else { return };
+        let active = unsafe { lvgl_sys::lv_disp_get_scr_act(lvgl_sys::lv_disp_get_default()) };
+        // A screen loaded behind the manager's back may load one of ours
+        // again when it is done: leave them all alone until it is. Also
+        // skips the odd pass mid-transition, which is harmless.
+        if active != self.top().root {
+            return;
+        }
+        // `current` is never torn down: the stack pops back to it.
+        for (i, entry) in self.entries.iter_mut().enumerate() {
+            let idle = entry.left_at.is_some_and(|t| t.elapsed() >= after);
+            let busy = entry.root == active || transition::involves(entry.root);
+            if i != self.current && idle && !entry.root.is_null() && !busy {
+                entry.teardown();
+                log::info!("screen {i} torn down after {}s unvisited", after.as_secs());
+            }
+        }
+    }
+}
+
+impl Drop for ScreenManager {
+    fn drop(&mut self) {
+        for entry in self.stack.iter_mut().chain(&mut self.retired).chain(&mut self.entries) {
+            entry.teardown();
+        }
+    }
+}
+
+unsafe fn create_root() -> *mut lvgl_sys::lv_obj_t {
+    let root = lvgl_sys::lv_obj_create(core::ptr::null_mut());
+    // LVGL sends LV_EVENT_GESTURE to the screen when a drag exceeds
+    // LV_INDEV_DEF_GESTURE_LIMIT (default 50px).
+    lvgl_sys::lv_obj_add_event_cb(
+        root,
+        Some(gesture_cb),
+        lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE,
+        core::ptr::null_mut(),
+    );
+    root
+}
+
+fn with_manager(f: impl FnOnce(&mut ScreenManager)) {
+    MANAGER.with(|m| {
+        if let Some(manager) = m.borrow_mut().as_mut() {
+            f(manager);
+        }
+    });
+}
+
+/// Swipe between neighbouring screens: left → next, right → previous.
+/// Down from the top edge opens quick settings, up from the bottom edge
+/// notifications.
+pub fn navigate(dir: lvgl_sys::lv_dir_t) {
+    with_manager(|m| m.navigate(dir));
+}
+
+/// Forward a long press to the visible screen, unless an overlay covers it.
+pub fn long_press() {
+    with_manager(|m| {
+        if m.overlay.is_none() {
+            m.top().screen.on_long_press();
+        }
+    });
+}
+
+/// Open `screen` on top of the visible one; a back swipe from the left
+/// bezel, or `pop`, returns. Takes effect on the next LVGL timer pass, so
+/// it is safe to call from screen hooks and event callbacks.
+pub fn push(screen: impl Screen + 'static) {
+    let screen: Box<Box<dyn Screen>> = Box::new(Box::new(screen));
+    unsafe { lvgl_sys::lv_async_call(Some(push_cb), Box::into_raw(screen) as *mut _) };
+}
+
+/// Return to the screen below the top of the stack, if there is one.
+/// Deferred like `push`.
+pub fn pop() {
+    unsafe { lvgl_sys::lv_async_call(Some(pop_cb), core::ptr::null_mut()) };
+}
+
+unsafe extern "C" fn push_cb(screen: *mut core::ffi::c_void) {
+    let screen = Box::from_raw(screen as *mut Box<dyn Screen>);
+    with_manager(|m| m.push(*screen));
+}
+
+unsafe extern "C" fn pop_cb(_: *mut core::ffi::c_void) {
+    with_manager(|m| m.pop());
+}
+
+unsafe extern "C" fn reclaim_cb(_timer: *mut lvgl_sys::lv_timer_t) {
+    with_manager(|m| m.reclaim());
+}
+
+unsafe extern "C" fn drag_cb(_timer: *mut lvgl_sys::lv_timer_t) {
+    with_manager(|m| m.track_drag());
+}
+
+/// `false` if the finger is on a widget that handles drags itself: one
+/// being scrolled, or one that does not pass gestures up to its screen.
+fn drag_allowed() -> bool {
+    unsafe {
+        let indev = lvgl_sys::lv_indev_get_next(core::ptr::null_mut());
+        if indev.is_null() {
+            return true;
+        }
+        if !lvgl_sys::lv_indev_get_scroll_obj(indev).is_null() {
+            return false;
+        }
+        let mut obj = (*indev).proc.types.pointer.act_obj;
+        while !obj.is_null() {
+            let parent = lvgl_sys::lv_obj_get_parent(obj);
+            if !parent.is_null() && !lvgl_sys::lv_obj_has_flag(obj, lvgl_sys::LV_OBJ_FLAG_GESTURE_BUBBLE) {
+                return false;
+            }
+            obj = parent;
+        }
+        true
+    }
+}
+
+/// Gesture event callback attached to every screen root and overlay panel.
+/// Ignored when the touch controller's gesture engine is in charge.
+unsafe extern "C" fn gesture_cb(_e: *mut lvgl_sys::lv_event_t) {
+    if crate::touch::hw_gestures_enabled() {
+        return;
+    }
+    let indev = lvgl_sys::lv_indev_get_act();
+    if indev.is_null() {
+        return;
+    }
+    navigate(lvgl_sys::lv_indev_get_gesture_dir(indev));
+}
@@ -0,0 +1,279 @@
+// src/screens/overlay.rs
+// Panels that slide over the current screen on lv_layer_top: quick settings
+// from the top edge, notifications from the bottom. The screen underneath
+// stays loaded and keeps animating.
+
+use std::ffi::CString;
+
+use crate::{notifications, safe_area, settings};
+
+/// Swipes must start this close to the top/bottom edge to open an overlay.
+pub const EDGE_PX: i32 = 60;
+
+const SLIDE_MS: u32 = 200;
+const PANEL_BG: (u8, u8, u8) = (0x10, 0x14, 0x1c);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayKind {
+    /// Brightness, Wi-Fi, do-not-disturb. Pulled down from the top.
+    QuickSettings,
+    /// Notification list. Pulled up from the bottom.
+    Notifications,
+}
+
+impl OverlayKind {
+    /// The overlay a swipe in `dir` starting at `start_y` opens, if any.
+    pub fn for_swipe(dir: lvgl_sys::lv_dir_t, start_y: i32) -> Option<Self> {
+        if dir == lvgl_sys::LV_DIR_BOTTOM as lvgl_sys::lv_dir_t && start_y < EDGE_PX {
+            Some(OverlayKind::QuickSettings)
+        } else if dir == lvgl_sys::LV_DIR_TOP as lvgl_sys::lv_dir_t
+            && start_y >= safe_area::DISPLAY_H - EDGE_PX
+        {
+            Some(OverlayKind::Notifications)
+        } else {
+            None
+        }
+    }
+
+    /// Swipe direction that puts the overlay away again.
+    pub fn close_dir(self) -> lvgl_sys::lv_dir_t {
+        match self {
+            OverlayKind::QuickSettings => lvgl_sys::LV_DIR_TOP as lvgl_sys::lv_dir_t,
+            OverlayKind::Notifications => lvgl_sys::LV_DIR_BOTTOM as lvgl_sys::lv_dir_t,
+        }
+    }
+
+    /// Panel y position while hidden.
+    fn hidden_y(self) -> i32 {
+        match self {
+            OverlayKind::QuickSettings => -safe_area::DISPLAY_H,
+            OverlayKind::Notifications => safe_area::DISPLAY_H,
+        }
+    }
+}
+
+/// An open overlay. Closing it slides the panel out and deletes it.
+pub struct Overlay {
+    pub kind: OverlayKind,
+    panel: *mut lvgl_sys::lv_obj_t,
+}
+
+impl Overlay {
+    /// Build the panel for `kind` and slide it in. `gesture_cb` receives
+    /// swipes made on the panel.
+    pub fn open(kind: OverlayKind, gesture_cb: lvgl_sys::lv_event_cb_t) -> Self {
+        unsafe {
+            let layer = lvgl_sys::lv_disp_get_layer_top(lvgl_sys::lv_disp_get_default());
+            let panel = lvgl_sys::lv_obj_create(layer);
+            lvgl_sys::lv_obj_set_size(
+                panel,
+                safe_area::DISPLAY_W as lvgl_sys::lv_coord_t,
+                safe_area::DISPLAY_H as lvgl_sys::lv_coord_t,
+            );
+            lvgl_sys::lv_obj_set_pos(panel, 0, kind.hidden_y() as lvgl_sys::lv_coord_t);
+            lvgl_sys::lv_obj_set_style_bg_color(
+                panel,
+                lvgl_sys::_LV_COLOR_MAKE(PANEL_BG.0, PANEL_BG.1, PANEL_BG.2),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_set_style_border_width(panel, 0, lvgl_sys::LV_STATE_DEFAULT);
+            lvgl_sys::lv_obj_set_style_radius(panel, 0, lvgl_sys::LV_STATE_DEFAULT);
+            lvgl_sys::lv_obj_clear_flag(panel, lvgl_sys::LV_OBJ_FLAG_SCROLLABLE);
+            lvgl_sys::lv_obj_add_event_cb(
+                panel,
+                gesture_cb,
+                lvgl_sys::lv_event_code_t_LV_EVENT_GESTURE,
+                core::ptr::null_mut(),
+            );
+
+            match kind {
+                OverlayKind::QuickSettings => build_quick_settings(panel),
+                OverlayKind::Notifications => build_notifications(panel),
+            }
+            slide(panel, kind.hidden_y(), 0, false);
+            log::debug!("overlay {kind:?} opened");
+            Self { kind, panel }
+        }
+    }
+
+    /// Slide the panel out; it is deleted when the animation ends.
+    pub fn close(self) {
+        unsafe { slide(self.panel, 0, self.kind.hidden_y(), true) };
+        log::debug!("overlay {:?} closed", self.kind);
+    }
+}
+
+// ── Slide animation ───────────────────────────────────────────────────────
+
+unsafe extern "C" fn anim_set_y(obj: *mut core::ffi::c_void, y: i32) {
+    lvgl_sys::lv_obj_set_y(obj as *mut lvgl_sys::lv_obj_t, y as lvgl_sys::lv_coord_t);
+}
+
+unsafe extern "C" fn anim_delete_obj(a: *mut lvgl_sys::lv_anim_t) {
+    lvgl_sys::lv_obj_del((*a).var as *mut lvgl_sys::lv_obj_t);
+}
+
+unsafe fn slide(panel: *mut lvgl_sys::lv_obj_t, from: i32, to: i32, delete: bool) {
+    let mut a: lvgl_sys::lv_anim_t = core::mem::zeroed();
+    lvgl_sys::lv_anim_init(&mut a);
+    a.var = panel as *mut _;
+    a.exec_cb = Some(anim_set_y);
+    a.start_value = from;
+    a.current_value = from;
+    a.end_value = to;
+    a.time = SLIDE_MS;
+    a.path_cb = Some(lvgl_sys::lv_anim_path_ease_out);
+    if delete {
+        a.ready_cb = Some(anim_delete_obj);
+    }
+    // Copied by LVGL.
+    lvgl_sys::lv_anim_start(&a);
+}
+
+// ── Content ───────────────────────────────────────────────────────────────
+
+unsafe fn label(parent: *mut lvgl_sys::lv_obj_t, text: &str) -> *mut lvgl_sys::lv_obj_t {
+    let label = lvgl_sys::lv_label_create(parent);
+    let text = CString::new(text).unwrap_or_default();
+    // lv_label_set_text copies the string.
+    lvgl_sys::lv_label_set_text(label, text.as_ptr());
+    lvgl_sys::lv_obj_set_style_text_color(
+        label,
+        lvgl_sys::_LV_COLOR_MAKE(0xFF, 0xFF, 0xFF),
+        lvgl_sys::LV_STATE_DEFAULT,
+    );
+    label
+}
+
+/// Transparent 300 px wide flex column centred on `panel`: everything
+/// inside stays clear of the round edge.
+unsafe fn column(panel: *mut lvgl_sys::lv_obj_t, height: i16) -> *mut lvgl_sys::lv_obj_t {
+    let col = lvgl_sys::lv_obj_create(panel);
+    lvgl_sys::lv_obj_set_size(col, 300, height);
+    lvgl_sys::lv_obj_align(col, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
+    lvgl_sys::lv_obj_set_style_bg_opa(col, lvgl_sys::LV_OPA_TRANSP as u8, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_set_style_border_width(col, 0, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_set_style_pad_row(col, 16, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_set_flex_flow(col, lvgl_sys::lv_flex_flow_t_LV_FLEX_FLOW_COLUMN);
+    lvgl_sys::lv_obj_set_flex_align(
+        col,
+        lvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_CENTER,
+        lvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_CENTER,
+        lvgl_sys::lv_flex_align_t_LV_FLEX_ALIGN_CENTER,
+    );
+    col
+}
+
+/// Label + switch on one row; `on_change` gets the new state.
+unsafe fn toggle_row(
+    parent: *mut lvgl_sys::lv_obj_t,
+    text: &str,
+    on: bool,
+    on_change: unsafe extern "C" fn(*mut lvgl_sys::lv_event_t),
+) {
+    let row = lvgl_sys::lv_obj_create(parent);
+    lvgl_sys::lv_obj_set_size(row, 260, 48);
+    lvgl_sys::lv_obj_set_style_bg_opa(row, lvgl_sys::LV_OPA_TRANSP as u8, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_set_style_border_width(row, 0, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_clear_flag(row, lvgl_sys::LV_OBJ_FLAG_SCROLLABLE);
+
+    let l = label(row, text);
+    lvgl_sys::lv_obj_align(l, lvgl_sys::LV_ALIGN_LEFT_MID as u8, 0, 0);
+
+    let sw = lvgl_sys::lv_switch_create(row);
+    lvgl_sys::lv_obj_align(sw, lvgl_sys::LV_ALIGN_RIGHT_MID as u8, 0, 0);
+    if on {
+        lvgl_sys::lv_obj_add_state(sw, lvgl_sys::LV_STATE_CHECKED as lvgl_sys::lv_state_t);
+    }
+    lvgl_sys::lv_obj_add_event_cb(
+        sw,
+        Some(on_change),
+        lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED,
+        core::ptr::null_mut(),
+    );
+}
+
+unsafe fn switch_state(e: *mut lvgl_sys::lv_event_t) -> bool {
+    let sw = lvgl_sys::lv_event_get_target(e);
+    lvgl_sys::lv_obj_has_state(sw, lvgl_sys::LV_STATE_CHECKED as lvgl_sys::lv_state_t)
+}
+
+unsafe fn build_quick_settings(panel: *mut lvgl_sys::lv_obj_t) {
+    let col = column(panel, 280);
+    label(col, "Quick settings");
+
+    label(col, "Brightness");
+    let slider = lvgl_sys::lv_slider_create(col);
+    lvgl_sys::lv_obj_set_width(slider, 240);
+    // Bottom of the range stays visible; 0 would black the panel out.
+    lvgl_sys::lv_bar_set_range(slider, 0x10, 0xFF);
+    lvgl_sys::lv_bar_set_value(
+        slider,
+        settings::brightness() as i32,
+        lvgl_sys::lv_anim_enable_t_LV_ANIM_OFF,
+    );
+    lvgl_sys::lv_obj_add_event_cb(
+        slider,
+        Some(brightness_cb),
+        lvgl_sys::lv_event_code_t_LV_EVENT_VALUE_CHANGED,
+        core::ptr::null_mut(),
+    );
+
+    toggle_row(col, "Wi-Fi", settings::wifi_enabled(), wifi_cb);
+    toggle_row(col, "Do not disturb", settings::dnd(), dnd_cb);
+}
+
+unsafe extern "C" fn brightness_cb(e: *mut lvgl_sys::lv_event_t) {
+    let slider = lvgl_sys::lv_event_get_target(e);
+    let level = lvgl_sys::lv_bar_get_value(slider).clamp(0, 0xFF) as u8;
+    // Applied by the main loop's auto-dim on its next pass; the display
+    // cannot be touched from inside lv_timer_handler.
+    settings::set_brightness(level);
+}
+
+unsafe extern "C" fn wifi_cb(e: *mut lvgl_sys::lv_event_t) {
+    settings::set_wifi_enabled(switch_state(e));
+}
+
+unsafe extern "C" fn dnd_cb(e: *mut lvgl_sys::lv_event_t) {
+    settings::set_dnd(switch_state(e));
+}
+
+unsafe fn build_notifications(panel: *mut lvgl_sys::lv_obj_t) {
+    let col = column(panel, 340);
+    label(col, "Notifications");
+
+    let list = lvgl_sys::lv_list_create(col);
+    lvgl_sys::lv_obj_set_size(list, 280, 220);
+    fill_list(list);
+
+    let clear = lvgl_sys::lv_btn_create(col);
+    let l = label(clear, "Clear all");
+    lvgl_sys::lv_obj_align(l, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
+    lvgl_sys::lv_obj_add_event_cb(
+        clear,
+        Some(clear_cb),
+        lvgl_sys::lv_event_code_t_LV_EVENT_CLICKED,
+        list as *mut _,
+    );
+}
+
+unsafe fn fill_list(list: *mut lvgl_sys::lv_obj_t) {
+    lvgl_sys::lv_obj_clean(list);
+    let all = notifications::snapshot();
+    if all.is_empty() {
+        lvgl_sys::lv_list_add_text(list, b"No notifications\0".as_ptr() as *const i8);
+        return;
+    }
+    for n in all {
+        let title = CString::new(n.title).unwrap_or_default();
+        let body = CString::new(n.body).unwrap_or_default();
+        lvgl_sys::lv_list_add_text(list, title.as_ptr());
+        lvgl_sys::lv_list_add_btn(list, core::ptr::null(), body.as_ptr());
+    }
+}
+
+unsafe extern "C" fn clear_cb(e: *mut lvgl_sys::lv_event_t) {
+    notifications::clear();
+    fill_list(lvgl_sys::lv_event_get_user_data(e) as *mut lvgl_sys::lv_obj_t);
+}
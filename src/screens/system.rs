@@ -0,0 +1,78 @@
+// src/screens/system.rs
+// Detail page pushed from the info screen: free memory, refreshed on every
+// entry. Opens with an iris; a back swipe from the left bezel closes it.
+
+use std::ffi::CString;
+
+use esp_idf_svc::sys;
+
+use super::transition::{Easing, Transition, TransitionKind};
+use super::Screen;
+
+pub struct SystemScreen {
+    label: *mut lvgl_sys::lv_obj_t,
+}
+
+impl Default for SystemScreen {
+    fn default() -> Self {
+        Self { label: core::ptr::null_mut() }
+    }
+}
+
+fn summary() -> String {
+    // SAFETY: heap_caps queries have no preconditions.
+    let (internal, largest, psram) = unsafe {
+        (
+            sys::heap_caps_get_free_size(sys::MALLOC_CAP_INTERNAL),
+            sys::heap_caps_get_largest_free_block(sys::MALLOC_CAP_INTERNAL),
+            sys::heap_caps_get_free_size(sys::MALLOC_CAP_SPIRAM),
+        )
+    };
+    format!(
+        "Internal free: {} KB\nLargest block: {} KB\nPSRAM free: {} KB",
+        internal / 1024,
+        largest / 1024,
+        psram / 1024
+    )
+}
+
+impl Screen for SystemScreen {
+    fn create(&mut self, root: *mut lvgl_sys::lv_obj_t) {
+        unsafe {
+            lvgl_sys::lv_obj_set_style_bg_color(
+                root,
+                lvgl_sys::_LV_COLOR_MAKE(0x20, 0x20, 0x20),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+            lvgl_sys::lv_obj_set_style_text_color(
+                root,
+                lvgl_sys::_LV_COLOR_MAKE(0xFF, 0xFF, 0xFF),
+                lvgl_sys::LV_STATE_DEFAULT,
+            );
+
+            let title = lvgl_sys::lv_label_create(root);
+            lvgl_sys::lv_label_set_text(title, b"System\0".as_ptr() as *const i8);
+            lvgl_sys::lv_obj_align(title, lvgl_sys::LV_ALIGN_TOP_MID as u8, 0, 80);
+
+            self.label = lvgl_sys::lv_label_create(root);
+            lvgl_sys::lv_obj_align(self.label, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
+        }
+    }
+
+    fn on_enter(&mut self) {
+        let text = CString::new(summary()).unwrap_or_default();
+        unsafe {
+            lvgl_sys::lv_label_set_text(self.label, text.as_ptr());
+            // Re-centre for the new text width.
+            lvgl_sys::lv_obj_align(self.label, lvgl_sys::LV_ALIGN_CENTER as u8, 0, 0);
+        }
+    }
+
+    fn destroy(&mut self) {
+        self.label = core::ptr::null_mut();
+    }
+
+    fn transition(&self) -> Option<Transition> {
+        Some(Transition::new(TransitionKind::Iris).duration(300).easing(Easing::EaseOut))
+    }
+}
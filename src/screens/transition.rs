@@ -0,0 +1,283 @@
+// src/screens/transition.rs
+// Screen transitions. Both screens are rendered during a transition (LVGL
+// draws `prev_scr` under `act_scr`); one frame is a function of progress
+// 0..=1000, so the same code runs a timed animation or follows a finger.
+// LVGL thread only; at most one transition runs at a time.
+
+use std::cell::RefCell;
+
+pub use crate::anim::Easing;
+use crate::{anim, safe_area};
+
+/// Progress at which the incoming screen fully covers the display.
+pub const DONE: i32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// Both screens slide; the incoming one pushes the other out.
+    Move,
+    /// The incoming screen slides over the outgoing one.
+    Over,
+    /// The incoming screen fades in on top.
+    Fade,
+    /// The incoming screen is revealed through a circle growing from the
+    /// centre of the display.
+    Iris,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub duration_ms: u32,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub const fn new(kind: TransitionKind) -> Self {
+        Self { kind, duration_ms: 150, easing: Easing::Linear }
+    }
+
+    pub const fn duration(mut self, ms: u32) -> Self {
+        self.duration_ms = ms;
+        self
+    }
+
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl Default for Transition {
+    /// A 150 ms linear slide.
+    fn default() -> Self {
+        Self::new(TransitionKind::Move)
+    }
+}
+
+struct Running {
+    from: *mut lvgl_sys::lv_obj_t,
+    to: *mut lvgl_sys::lv_obj_t,
+    /// `to` comes in from the right. Backwards, `Over`, `Fade` and `Iris`
+    /// play in reverse: `from` stays on top and leaves.
+    forward: bool,
+    transition: Transition,
+    progress: i32,
+    /// Where the transition settles: `DONE` shows `to`, 0 returns to `from`.
+    target: i32,
+    // Iris mask, valid between DRAW_MAIN_BEGIN and DRAW_POST_END of the
+    // top screen.
+    mask: lvgl_sys::lv_draw_mask_radius_param_t,
+    mask_id: i16,
+}
+
+impl Running {
+    /// The screen drawn on top, the one underneath, and how much of the
+    /// display the top one covers (0..=`DONE`).
+    fn layers(&self) -> (*mut lvgl_sys::lv_obj_t, *mut lvgl_sys::lv_obj_t, i32) {
+        if self.forward || self.transition.kind == TransitionKind::Move {
+            (self.to, self.from, self.progress)
+        } else {
+            (self.from, self.to, DONE - self.progress)
+        }
+    }
+}
+
+thread_local! {
+    static RUNNING: RefCell<Option<Box<Running>>> = const { RefCell::new(None) };
+}
+
+/// `true` while a transition is being animated or dragged.
+pub fn is_running() -> bool {
+    RUNNING.with(|r| r.borrow().is_some())
+}
+
+/// `true` if `root` is one of the screens of the running transition.
+pub fn involves(root: *mut lvgl_sys::lv_obj_t) -> bool {
+    RUNNING.with(|r| r.borrow().as_ref().is_some_and(|t| t.from == root || t.to == root))
+}
+
+/// Start a transition from `from` to `to` at progress 0, without animating.
+/// Drive it with `set_progress` and settle it with `animate_to`. A running
+/// transition is first completed instantly.
+pub fn begin(
+    from: *mut lvgl_sys::lv_obj_t,
+    to: *mut lvgl_sys::lv_obj_t,
+    forward: bool,
+    transition: Transition,
+) {
+    finish_now();
+    let running = Box::new(Running {
+        from,
+        to,
+        forward,
+        transition,
+        progress: 0,
+        target: 0,
+        // SAFETY: plain C struct, initialised before use by the draw callback.
+        mask: unsafe { core::mem::zeroed() },
+        mask_id: -1,
+    });
+    let (top, bottom, _) = running.layers();
+    unsafe {
+        // LVGL draws `prev_scr` first, then `act_scr` over it.
+        let disp = lvgl_sys::lv_disp_get_default();
+        (*disp).prev_scr = bottom;
+        (*disp).act_scr = top;
+        if transition.kind == TransitionKind::Iris {
+            lvgl_sys::lv_obj_add_event_cb(
+                top,
+                Some(iris_draw_cb),
+                lvgl_sys::lv_event_code_t_LV_EVENT_ALL,
+                core::ptr::null_mut(),
+            );
+        }
+        apply(&running);
+    }
+    RUNNING.with(|r| *r.borrow_mut() = Some(running));
+}
+
+/// Move the running transition to `progress` (0..=`DONE`; a little either
+/// side is allowed for overshooting easings).
+pub fn set_progress(progress: i32) {
+    with_running(|t| {
+        t.progress = progress.clamp(-DONE / 4, DONE + DONE / 4);
+        unsafe { apply(t) };
+    });
+}
+
+/// Animate the running transition to `target` (0 or `DONE`) with its
+/// easing, over the share of its duration that is left. It ends there.
+pub fn animate_to(target: i32) {
+    let Some((var, from, duration, easing)) = with_running(|t| {
+        t.target = target;
+        let left = (target - t.progress).unsigned_abs() * t.transition.duration_ms / DONE as u32;
+        (t.to, t.progress, left.max(1), t.transition.easing)
+    }) else {
+        return;
+    };
+    unsafe {
+        let mut a: lvgl_sys::lv_anim_t = core::mem::zeroed();
+        lvgl_sys::lv_anim_init(&mut a);
+        a.var = var as *mut _;
+        a.exec_cb = Some(anim_exec);
+        a.start_value = from;
+        a.current_value = from;
+        a.end_value = target;
+        a.time = duration;
+        a.path_cb = anim::path_cb(easing);
+        a.ready_cb = Some(anim_ready);
+        lvgl_sys::lv_anim_start(&a);
+    }
+}
+
+/// Run a whole transition from `from` to `to`.
+pub fn run(
+    from: *mut lvgl_sys::lv_obj_t,
+    to: *mut lvgl_sys::lv_obj_t,
+    forward: bool,
+    transition: Transition,
+) {
+    begin(from, to, forward, transition);
+    animate_to(DONE);
+}
+
+/// Jump the running transition, if any, to its target and end it.
+pub fn finish_now() {
+    let Some(var) = with_running(|t| t.to) else {
+        return;
+    };
+    unsafe { lvgl_sys::lv_anim_del(var as *mut _, Some(anim_exec)) };
+    finish();
+}
+
+fn with_running<R>(f: impl FnOnce(&mut Running) -> R) -> Option<R> {
+    RUNNING.with(|r| r.borrow_mut().as_deref_mut().map(f))
+}
+
+/// Put both screens where `t.progress` says.
+unsafe fn apply(t: &Running) {
+    let w = safe_area::DISPLAY_W;
+    let (top, _, cover) = t.layers();
+    match t.transition.kind {
+        TransitionKind::Move => {
+            // The side `to` enters from.
+            let side = if t.forward { 1 } else { -1 };
+            let p = t.progress;
+            lvgl_sys::lv_obj_set_x(t.to, (side * w * (DONE - p) / DONE) as lvgl_sys::lv_coord_t);
+            lvgl_sys::lv_obj_set_x(t.from, (-side * w * p / DONE) as lvgl_sys::lv_coord_t);
+        }
+        // In from the right, or back out to the right.
+        TransitionKind::Over => {
+            lvgl_sys::lv_obj_set_x(top, (w * (DONE - cover) / DONE) as lvgl_sys::lv_coord_t);
+        }
+        TransitionKind::Fade => {
+            let opa = (cover.clamp(0, DONE) * lvgl_sys::LV_OPA_COVER as i32 / DONE) as lvgl_sys::lv_opa_t;
+            lvgl_sys::lv_obj_set_style_opa(top, opa, lvgl_sys::LV_STATE_DEFAULT);
+        }
+        TransitionKind::Iris => lvgl_sys::lv_obj_invalidate(top),
+    }
+}
+
+/// Undo everything `apply` did to `root`.
+unsafe fn reset(root: *mut lvgl_sys::lv_obj_t) {
+    lvgl_sys::lv_obj_set_x(root, 0);
+    lvgl_sys::lv_obj_set_style_opa(root, lvgl_sys::LV_OPA_COVER as lvgl_sys::lv_opa_t, lvgl_sys::LV_STATE_DEFAULT);
+    lvgl_sys::lv_obj_remove_event_cb(root, Some(iris_draw_cb));
+}
+
+/// End the running transition at its target.
+fn finish() {
+    let Some(t) = RUNNING.with(|r| r.borrow_mut().take()) else {
+        return;
+    };
+    let shown = if t.target == DONE { t.to } else { t.from };
+    unsafe {
+        reset(t.from);
+        reset(t.to);
+        let disp = lvgl_sys::lv_disp_get_default();
+        (*disp).prev_scr = core::ptr::null_mut();
+        (*disp).act_scr = shown;
+        lvgl_sys::lv_obj_invalidate(shown);
+    }
+}
+
+unsafe extern "C" fn anim_exec(_var: *mut core::ffi::c_void, progress: i32) {
+    set_progress(progress);
+}
+
+unsafe extern "C" fn anim_ready(_a: *mut lvgl_sys::lv_anim_t) {
+    finish();
+}
+
+/// Clips the top screen to the iris circle while it draws.
+unsafe extern "C" fn iris_draw_cb(e: *mut lvgl_sys::lv_event_t) {
+    let code = lvgl_sys::lv_event_get_code(e);
+    with_running(|t| {
+        if code == lvgl_sys::lv_event_code_t_LV_EVENT_COVER_CHECK {
+            // The screen underneath shows around the circle.
+            let info = lvgl_sys::lv_event_get_param(e) as *mut lvgl_sys::lv_cover_check_info_t;
+            (*info).res = lvgl_sys::lv_cover_res_t_LV_COVER_RES_MASKED;
+        } else if code == lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_MAIN_BEGIN {
+            let (_, _, cover) = t.layers();
+            let r = (safe_area::DISPLAY_R + 1) * cover.max(0) / DONE;
+            let area = lvgl_sys::lv_area_t {
+                x1: (safe_area::DISPLAY_CX - r) as lvgl_sys::lv_coord_t,
+                y1: (safe_area::DISPLAY_CY - r) as lvgl_sys::lv_coord_t,
+                x2: (safe_area::DISPLAY_CX + r) as lvgl_sys::lv_coord_t,
+                y2: (safe_area::DISPLAY_CY + r) as lvgl_sys::lv_coord_t,
+            };
+            lvgl_sys::lv_draw_mask_radius_init(
+                &mut t.mask,
+                &area,
+                lvgl_sys::LV_RADIUS_CIRCLE as lvgl_sys::lv_coord_t,
+                false,
+            );
+            t.mask_id = lvgl_sys::lv_draw_mask_add(&mut t.mask as *mut _ as *mut _, core::ptr::null_mut());
+        } else if code == lvgl_sys::lv_event_code_t_LV_EVENT_DRAW_POST_END && t.mask_id >= 0 {
+            lvgl_sys::lv_draw_mask_remove_id(t.mask_id);
+            lvgl_sys::lv_draw_mask_free_param(&mut t.mask as *mut _ as *mut _);
+            t.mask_id = -1;
+        }
+    });
+}
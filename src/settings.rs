@@ -0,0 +1,39 @@
+// src/settings.rs
+// User settings toggled from the quick-settings overlay. Plain atomics so
+// the main loop and other tasks can read them without locking.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+static BRIGHTNESS: AtomicU8 = AtomicU8::new(0xFF);
+static WIFI: AtomicBool = AtomicBool::new(false);
+static DND: AtomicBool = AtomicBool::new(false);
+
+/// Brightness while active (0..=255); auto-dim works down from this.
+pub fn brightness() -> u8 {
+    BRIGHTNESS.load(Ordering::Relaxed)
+}
+
+pub fn set_brightness(level: u8) {
+    BRIGHTNESS.store(level, Ordering::Relaxed);
+}
+
+/// Wi-Fi preference. There is no Wi-Fi stack yet; this only records the
+/// user's choice for when there is.
+pub fn wifi_enabled() -> bool {
+    WIFI.load(Ordering::Relaxed)
+}
+
+pub fn set_wifi_enabled(on: bool) {
+    WIFI.store(on, Ordering::Relaxed);
+    log::info!("Wi-Fi {}", if on { "on" } else { "off" });
+}
+
+/// Do-not-disturb: notifications are still listed but do not wake the
+/// display.
+pub fn dnd() -> bool {
+    DND.load(Ordering::Relaxed)
+}
+
+pub fn set_dnd(on: bool) {
+    DND.store(on, Ordering::Relaxed);
+}
@@ -14,17 +14,20 @@ pub const CMD_H:  i32 = 80;
 pub const BLINK_W: i32 = 20;
 pub const BLINK_H: i32 = 10;
 
+/// Fill around the characters in their frames; not part of their hit masks.
+pub const SPRITE_BG: u16 = BG_DARK;
+
 // ─── Color constants (byte-swapped RGB565, LV_COLOR_16_SWAP=1) ──────────────
 const BG_DARK:    u16 = 0x0819; // dark navy floor — used as sprite background fill
 const SCREEN_BLU: u16 = 0x5F06;
 const SCREEN_GRN: u16 = 0xF107;
 const UNIFORM_DK: u16 = 0x2121; // deep navy uniform
-#[allow(dead_code)]
-const UNIFORM_LT: u16 = 0x4229; // lighter uniform detail
 const SKIN:       u16 = rgb565_const(0xdf, 0xa6, 0x8d);
 const HAIR_BROWN: u16 = 0x2143;
 const GOLD_TRIM:  u16 = 0x05E6; // command yellow/gold
 const CONSOLE_GY: u16 = 0x9492; // console metal grey
+const WARN_RED:   u16 = rgb565_const(0xff, 0x22, 0x00); // red alert
+const WARN_DIM:   u16 = rgb565_const(0x60, 0x0c, 0x00); // red alert, off phase
 
 // ─── Utility ─────────────────────────────────────────────────────────────────
 const fn in_rect(x: i32, y: i32, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
@@ -49,27 +52,39 @@ const fn rgb565_const(r: u8, g: u8, b: u8) -> u16 {
 // ─── Crew sprites (48×64) ────────────────────────────────────────────────────
 // Top-down view of back-of-head and shoulders; crew face away toward consoles.
 // Transparent areas filled with BG_DARK (matches dark floor/wall background).
-const fn crew_pixel(x: i32, y: i32) -> u16 {
+const fn crew_pixel(x: i32, y: i32, frame: u8) -> u16 {
+    // Frame 1: typing — head dipped toward the console, forearms raised.
+    // Frame 2: turned round toward the commander, face showing.
+    let bob = if frame == 1 { 2 } else { 0 };
+    let typing = frame == 1;
+    let turned = frame == 2;
+
     let in_shoulders = in_rect(x, y, 8, 35, 40, 55);
-    let in_neck      = in_rect(x, y, 18, 30, 30, 35);
-    let in_head      = in_ellipse(x, y, 24, 25, 10, 10);
-    let in_hair      = in_ellipse(x, y, 24, 22, 10, 7);
+    let in_neck      = in_rect(x, y, 18, 30 + bob, 30, 35);
+    let in_head      = in_ellipse(x, y, 24, 25 + bob, 10, 10);
+    let in_hair      = if turned { in_ellipse(x, y, 24, 18, 10, 5) }
+                       else      { in_ellipse(x, y, 24, 22 + bob, 10, 7) };
+    let in_eyes      = turned && y == 27 && (x == 20 || x == 28);
+    let in_sleeves   = typing && (in_rect(x, y, 8, 29, 13, 35) || in_rect(x, y, 35, 29, 40, 35));
+    let in_hands     = typing && (in_rect(x, y, 8, 26, 13, 29) || in_rect(x, y, 35, 26, 40, 29));
     let in_chair     = in_rect(x, y, 12, 56, 36, 63);
 
-    if in_hair           { HAIR_BROWN }
-    else if in_head      { SKIN }
-    else if in_neck      { SKIN }
-    else if in_shoulders { UNIFORM_DK }
-    else if in_chair     { CONSOLE_GY }
-    else                 { BG_DARK }
+    if in_hair                             { HAIR_BROWN }
+    else if in_eyes                        { BG_DARK }
+    else if in_head || in_neck || in_hands { SKIN }
+    else if in_shoulders || in_sleeves     { UNIFORM_DK }
+    else if in_chair                       { CONSOLE_GY }
+    else                                   { BG_DARK }
 }
 
-const fn pixel_crew_a(x: i32, y: i32) -> u16 { crew_pixel(x, y) }
-const fn pixel_crew_b(x: i32, y: i32) -> u16 { crew_pixel(x, y) }
+const fn pixel_crew_a(x: i32, y: i32) -> u16 { crew_pixel(x, y, 0) }
+const fn pixel_crew_b(x: i32, y: i32) -> u16 { crew_pixel(x, y, 1) }
+const fn pixel_crew_turn(x: i32, y: i32) -> u16 { crew_pixel(x, y, 2) }
 
 // ─── Commander sprites (56×80) ───────────────────────────────────────────────
 // Front-facing commander with peaked hat, epaulets, and belt.
-// Transparent areas filled with BG_DARK. `frame` reserved for future animation.
+// Transparent areas filled with BG_DARK. Frames 0–2 are the idle pose;
+// frame 3 points ahead with the right arm.
 const fn cmd_pixel(x: i32, y: i32, frame: u8) -> u16 {
     let cx = 28; // horizontal center
 
@@ -88,11 +103,14 @@ const fn cmd_pixel(x: i32, y: i32, frame: u8) -> u16 {
     let in_torso     = in_rect(x, y, cx - 15, 42, cx + 15, 70);
     let in_belt      = in_rect(x, y, cx - 16, 58, cx + 16, 62);
 
-    // Suppress unused frame warning (reserved for future arm-pose animation)
-    let _ = frame;
+    // Pointing arm, raised from the right shoulder
+    let pointing  = frame == 3;
+    let in_sleeve = pointing && in_rect(x, y, cx + 22, 24, cx + 25, 36);
+    let in_hand   = pointing && in_rect(x, y, cx + 21, 17, cx + 26, 23);
 
     if in_hat_emblem                         { GOLD_TRIM }
-    else if in_hat_top || in_hat_brim        { UNIFORM_DK }
+    else if in_hand                          { SKIN }
+    else if in_hat_top || in_hat_brim || in_sleeve { UNIFORM_DK }
     else if in_eyes                          { BG_DARK }
     else if in_face                          { SKIN }
     else if in_epaulets                      { GOLD_TRIM }
@@ -104,10 +122,13 @@ const fn cmd_pixel(x: i32, y: i32, frame: u8) -> u16 {
 const fn pixel_cmd_a(x: i32, y: i32) -> u16 { cmd_pixel(x, y, 0) }
 const fn pixel_cmd_b(x: i32, y: i32) -> u16 { cmd_pixel(x, y, 1) }
 const fn pixel_cmd_c(x: i32, y: i32) -> u16 { cmd_pixel(x, y, 2) }
+const fn pixel_cmd_point(x: i32, y: i32) -> u16 { cmd_pixel(x, y, 3) }
 
 // ─── Console blink sprites (20×10) ───────────────────────────────────────────
 const fn pixel_blink_a(_x: i32, _y: i32) -> u16 { SCREEN_BLU }
 const fn pixel_blink_b(_x: i32, _y: i32) -> u16 { SCREEN_GRN }
+const fn pixel_blink_alert_a(_x: i32, _y: i32) -> u16 { WARN_RED }
+const fn pixel_blink_alert_b(_x: i32, _y: i32) -> u16 { WARN_DIM }
 
 // ─── Frame generators ────────────────────────────────────────────────────────
 const fn make_crew_frame_a() -> [u16; (CREW_W * CREW_H) as usize] {
@@ -126,6 +147,14 @@ const fn make_crew_frame_b() -> [u16; (CREW_W * CREW_H) as usize] {
         x += 1; } y += 1; }
     p
 }
+const fn make_crew_frame_turn() -> [u16; (CREW_W * CREW_H) as usize] {
+    let mut p = [BG_DARK; (CREW_W * CREW_H) as usize];
+    let mut y = 0i32;
+    while y < CREW_H { let mut x = 0i32; while x < CREW_W {
+        p[(y * CREW_W + x) as usize] = pixel_crew_turn(x, y);
+        x += 1; } y += 1; }
+    p
+}
 const fn make_cmd_frame(f: u8) -> [u16; (CMD_W * CMD_H) as usize] {
     let mut p = [BG_DARK; (CMD_W * CMD_H) as usize];
     let mut y = 0i32;
@@ -133,7 +162,8 @@ const fn make_cmd_frame(f: u8) -> [u16; (CMD_W * CMD_H) as usize] {
         p[(y * CMD_W + x) as usize] = match f {
             0 => pixel_cmd_a(x, y),
             1 => pixel_cmd_b(x, y),
-            _ => pixel_cmd_c(x, y),
+            2 => pixel_cmd_c(x, y),
+            _ => pixel_cmd_point(x, y),
         };
         x += 1; } y += 1; }
     p
@@ -142,15 +172,24 @@ const fn make_blink_frame(f: u8) -> [u16; (BLINK_W * BLINK_H) as usize] {
     let mut p = [SCREEN_BLU; (BLINK_W * BLINK_H) as usize];
     let mut y = 0i32;
     while y < BLINK_H { let mut x = 0i32; while x < BLINK_W {
-        p[(y * BLINK_W + x) as usize] = if f == 0 { pixel_blink_a(x, y) } else { pixel_blink_b(x, y) };
+        p[(y * BLINK_W + x) as usize] = match f {
+            0 => pixel_blink_a(x, y),
+            1 => pixel_blink_b(x, y),
+            2 => pixel_blink_alert_a(x, y),
+            _ => pixel_blink_alert_b(x, y),
+        };
         x += 1; } y += 1; }
     p
 }
 
 pub static CREW_FRAME_A: [u16; (CREW_W * CREW_H) as usize] = make_crew_frame_a();
 pub static CREW_FRAME_B: [u16; (CREW_W * CREW_H) as usize] = make_crew_frame_b();
+pub static CREW_FRAME_TURN: [u16; (CREW_W * CREW_H) as usize] = make_crew_frame_turn();
 pub static CMD_FRAME_A:  [u16; (CMD_W * CMD_H) as usize]  = make_cmd_frame(0);
 pub static CMD_FRAME_B:  [u16; (CMD_W * CMD_H) as usize]  = make_cmd_frame(1);
 pub static CMD_FRAME_C:  [u16; (CMD_W * CMD_H) as usize]  = make_cmd_frame(2);
+pub static CMD_FRAME_POINT: [u16; (CMD_W * CMD_H) as usize] = make_cmd_frame(3);
 pub static BLINK_FRAME_A: [u16; (BLINK_W * BLINK_H) as usize] = make_blink_frame(0);
 pub static BLINK_FRAME_B: [u16; (BLINK_W * BLINK_H) as usize] = make_blink_frame(1);
+pub static BLINK_ALERT_A: [u16; (BLINK_W * BLINK_H) as usize] = make_blink_frame(2);
+pub static BLINK_ALERT_B: [u16; (BLINK_W * BLINK_H) as usize] = make_blink_frame(3);
@@ -0,0 +1,165 @@
+// src/timeline.rs
+// Scripted scenes. A timeline is a text file of timed cues for named actors,
+// embedded with `include_str!` and parsed by `ui_core::timeline` when its
+// scene is built (the build script parses it too, so a broken one does not
+// get flashed); a `Player` runs it as the director of the scene's sprite
+// scheduler, so it pauses with the sprites. Besides the story, a timeline
+// can hold reactions: cues that run when an actor is tapped. The format is
+// described in `scenes/bridge.scene`.
+
+use ui_core::timeline::Action;
+pub use ui_core::timeline::{ParseError, Timeline};
+
+use crate::anim::{Clip, Easing, Path, Prop, SpriteId, Stage};
+
+/// A sprite a timeline can name, and the clips it can be told to play.
+pub struct Actor {
+    pub name: &'static str,
+    pub sprite: SpriteId,
+    pub clips: Vec<(&'static str, Clip)>,
+}
+
+/// An action bound to its clip.
+enum Cue {
+    Play(Clip),
+    Tween(Vec<(Prop, i32)>, u32, Easing),
+    Walk(Path),
+    Say(String, u32),
+}
+
+/// Cues in time order and how far through them playback is.
+#[derive(Default)]
+struct Track {
+    cues: Vec<(u32, SpriteId, Cue)>,
+    loop_ms: Option<u32>,
+    now_ms: u32,
+    next: usize,
+}
+
+impl Track {
+    /// `true` until every cue of a track that does not loop has fired.
+    fn is_running(&self) -> bool {
+        self.loop_ms.is_some() || self.next < self.cues.len()
+    }
+
+    fn restart(&mut self) {
+        self.now_ms = 0;
+        self.next = 0;
+    }
+
+    /// Let `dt_ms` pass, firing every cue that comes due. A finished track
+    /// stands still until `restart`.
+    fn advance(&mut self, dt_ms: u32, stage: &mut Stage) {
+        if !self.is_running() {
+            return;
+        }
+        self.now_ms += dt_ms;
+        loop {
+            while let Some((at_ms, sprite, cue)) = self.cues.get(self.next) {
+                if *at_ms > self.now_ms {
+                    break;
+                }
+                let sprite = stage.sprite(*sprite);
+                match cue {
+                    Cue::Play(clip) => sprite.play(clip),
+                    Cue::Tween(props, ms, easing) => {
+                        for &(prop, to) in props {
+                            sprite.tween(prop, to, *ms, *easing);
+                        }
+                    }
+                    Cue::Walk(path) => sprite.follow(path),
+                    Cue::Say(text, ms) => sprite.say(text, *ms),
+                }
+                self.next += 1;
+            }
+            match self.loop_ms {
+                Some(loop_ms) if self.now_ms >= loop_ms => {
+                    self.now_ms -= loop_ms;
+                    self.next = 0;
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+/// Runs a `Timeline` against a cast of actors. Hand `advance` to
+/// `Scheduler::set_director`.
+pub struct Player {
+    story: Track,
+    /// Per tapped sprite. Tapping it again while its reaction runs does
+    /// nothing.
+    reactions: Vec<(SpriteId, Track)>,
+}
+
+impl Player {
+    /// Bind every cue of `timeline` to its actor in `cast`. Fails on an
+    /// unknown actor or clip.
+    pub fn new(timeline: Timeline, cast: &[Actor]) -> Result<Self, ParseError> {
+        let mut story = Track { loop_ms: timeline.loop_ms, ..Track::default() };
+        let mut reactions: Vec<(SpriteId, Track)> = Vec::new();
+        for e in timeline.events {
+            let err = |msg: String| ParseError { line: e.line, msg };
+            let find = |name: &str| {
+                cast.iter().find(|a| a.name == name).ok_or_else(|| err(format!("unknown actor `{name}`")))
+            };
+            let actor = find(&e.actor)?;
+            let track = match &e.tapped {
+                None => &mut story,
+                Some(tapped) => {
+                    let tapped = find(tapped)?.sprite;
+                    let i = match reactions.iter().position(|(id, _)| *id == tapped) {
+                        Some(i) => i,
+                        None => {
+                            // Idle until tapped.
+                            let track = Track { next: usize::MAX, ..Track::default() };
+                            reactions.push((tapped, track));
+                            reactions.len() - 1
+                        }
+                    };
+                    &mut reactions[i].1
+                }
+            };
+            let cue = match e.action {
+                Action::Play(name) => {
+                    let (_, clip) = actor
+                        .clips
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .ok_or_else(|| err(format!("`{}` has no clip `{name}`", e.actor)))?;
+                    Cue::Play(clip.clone())
+                }
+                Action::Tween(props, ms, easing) => Cue::Tween(props, ms, easing),
+                Action::Walk(path) => Cue::Walk(path),
+                Action::Say(text, ms) => Cue::Say(text, ms),
+            };
+            track.cues.push((e.at_ms, actor.sprite, cue));
+        }
+        Ok(Self { story, reactions })
+    }
+
+    /// Sprites with a reaction: make them tappable.
+    pub fn tappable(&self) -> impl Iterator<Item = SpriteId> + '_ {
+        self.reactions.iter().map(|(id, _)| *id)
+    }
+
+    /// Let `dt_ms` pass: start the reactions to new taps, then fire every
+    /// cue that comes due.
+    pub fn advance(&mut self, dt_ms: u32, stage: &mut Stage) {
+        for tapped in stage.taps().to_vec() {
+            if let Some((_, track)) = self.reactions.iter_mut().find(|(id, _)| *id == tapped) {
+                if !track.is_running() {
+                    track.restart();
+                    // Cues at 0 s fire now.
+                    track.advance(0, stage);
+                }
+            }
+        }
+        self.story.advance(dt_ms, stage);
+        for (_, track) in &mut self.reactions {
+            if track.is_running() {
+                track.advance(dt_ms, stage);
+            }
+        }
+    }
+}
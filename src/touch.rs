@@ -0,0 +1,304 @@
+// src/touch.rs
+// Interrupt-driven touch input. The FT3168 pulses TP_INT low for every new
+// report; the GPIO ISR wakes a dedicated task which reads the controller and
+// publishes the primary touch point for the LVGL indev callback.
+
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use esp_idf_svc::hal::delay::TickType;
+use esp_idf_svc::hal::gpio::{AnyIOPin, Input, InterruptType, PinDriver};
+use esp_idf_svc::hal::task::notification::Notification;
+use esp_idf_svc::sys::{self, esp, EspError};
+use ft3168::{Ft3168, Gesture, InterruptMode, LongPressDetector, PowerMode, TouchEvent};
+use ui_core::touch_filter::{FilterConfig, TouchFilter};
+
+use crate::touch_bus::{BusError, TouchBus};
+use crate::touch_transform::TouchTransform;
+
+pub type Controller = Ft3168<TouchBus>;
+
+// Touch state written by the touch task, read by the LVGL indev callback on
+// the main thread: a `TouchState` packed into one word, so a reader never
+// pairs the press state with another contact's coordinates.
+static TOUCH: AtomicU64 = AtomicU64::new(0);
+
+// Where the current (or most recent) contact started, as `x << 16 | y`.
+// Edge swipes are told apart from swipes across the middle of the screen by
+// this point. Stored before the press is published in `TOUCH`.
+static PRESS_START: AtomicU32 = AtomicU32::new(0);
+
+/// The published touch state. Keeps the last coordinates after a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchState {
+    pub pressed: bool,
+    pub x: i32,
+    pub y: i32,
+}
+
+// The controller lives behind a mutex so power management on the main thread
+// can reconfigure it between reports. Reads hold the lock for one transaction.
+static CONTROLLER: Mutex<Option<Controller>> = Mutex::new(None);
+static TP_INT_GPIO: AtomicI32 = AtomicI32::new(-1);
+
+// Raw controller coordinates are mapped through this before publishing.
+static TRANSFORM: Mutex<TouchTransform> = Mutex::new(TouchTransform::IDENTITY);
+
+// Milliseconds since `EPOCH` at the last reported contact.
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+static LAST_TOUCH_MS: AtomicU32 = AtomicU32::new(0);
+
+// Latest recognised gesture, consumed by the main loop via `take_gesture`.
+// Only the most recent one is kept: a stale swipe is worse than a dropped one.
+static GESTURE: Mutex<Option<Gesture>> = Mutex::new(None);
+static HW_GESTURES: AtomicBool = AtomicBool::new(false);
+
+/// Long press: held this long without moving more than `LONG_PRESS_SLOP` px.
+const LONG_PRESS_MS: u32 = 600;
+const LONG_PRESS_SLOP: u16 = 12;
+
+/// While a finger is down (or a press/release is being debounced), re-read
+/// at least this often even without an INT edge, so a missed lift-up pulse
+/// cannot leave LVGL stuck in "pressed".
+const RELEASE_POLL_MS: u64 = 20;
+
+/// With no finger down, read the controller at least this often anyway, so
+/// one that stopped pulsing INT still fails reads and gets re-initialised.
+const IDLE_POLL_MS: u64 = 1000;
+
+const TASK_STACK: usize = 4096;
+
+/// Spawn the touch task. It owns the INT pin from here on; the controller is
+/// shared with `with_controller`.
+/// Set `hw_gestures` if the controller's gesture engine has been enabled;
+/// gestures are then read from the chip on every lift-off. Points pass
+/// through the transform and then a `TouchFilter` built from `filter`.
+pub fn spawn(
+    ft3168: Controller,
+    mut int: PinDriver<'static, AnyIOPin, Input>,
+    hw_gestures: bool,
+    filter: FilterConfig,
+) {
+    HW_GESTURES.store(hw_gestures, Ordering::Relaxed);
+    TP_INT_GPIO.store(int.pin(), Ordering::Relaxed);
+    *CONTROLLER.lock().unwrap() = Some(ft3168);
+    let start = *EPOCH.get_or_init(Instant::now);
+
+    std::thread::Builder::new()
+        .name("touch".into())
+        .stack_size(TASK_STACK)
+        .spawn(move || {
+            // The notification is bound to the task that creates it, so it
+            // must be set up here rather than in the spawning thread.
+            let notification = Notification::new();
+            let notifier = notification.notifier();
+            // SAFETY: the ISR callback only posts a task notification.
+            unsafe {
+                int.subscribe(move || {
+                    notifier.notify_and_yield(NonZeroU32::new(1).unwrap());
+                })
+                .expect("TP_INT subscribe failed");
+            }
+            int.set_interrupt_type(InterruptType::NegEdge)
+                .expect("TP_INT interrupt type failed");
+
+            let mut long_press = LongPressDetector::new(LONG_PRESS_MS, LONG_PRESS_SLOP);
+            let mut filter = TouchFilter::new(filter);
+            let mut last: Option<(u16, u16)> = None;
+            loop {
+                // esp-idf-hal disables the GPIO interrupt after each trigger.
+                int.enable_interrupt().expect("TP_INT enable failed");
+                let timeout_ms = if filter.is_active() {
+                    RELEASE_POLL_MS
+                } else {
+                    IDLE_POLL_MS
+                };
+                notification.wait(TickType::new_millis(timeout_ms).ticks());
+
+                let (result, reinit_pending) =
+                    with_controller(|ft| (ft.poll(), ft.reinit_pending()));
+                if reinit_pending {
+                    // Give the controller time to settle before the next poll
+                    // re-initialises it, without blocking the other users.
+                    std::thread::sleep(ft3168::STARTUP_DELAY);
+                }
+                let sample = match result {
+                    // LVGL's pointer indev only tracks one finger: feed it
+                    // the first point that is still in contact.
+                    Ok(touches) => touches
+                        .iter()
+                        .find(|p| p.event != TouchEvent::LiftUp)
+                        .map(|p| transform().apply(p.x, p.y)),
+                    // A garbled report says nothing about the finger: keep
+                    // the previous state rather than faking a release.
+                    Err(e) if e.is_implausible() => {
+                        log::debug!("{e}");
+                        continue;
+                    }
+                    Err(e) => {
+                        log::debug!("{e}");
+                        None
+                    }
+                };
+                let current = filter.update(sample);
+                let now_ms = start.elapsed().as_millis() as u32;
+                if current.is_some() {
+                    LAST_TOUCH_MS.store(now_ms, Ordering::Relaxed);
+                }
+                if current != last {
+                    publish(current);
+                }
+                if long_press.update(now_ms, current) {
+                    post_gesture(Gesture::LongPress);
+                }
+                // Swipes and double-tap complete on lift-off.
+                if hw_gestures && last.is_some() && current.is_none() {
+                    if let Ok(Some(g)) = with_controller(|ft| ft.read_gesture()) {
+                        post_gesture(g);
+                    }
+                }
+                last = current;
+            }
+        })
+        .expect("touch task spawn failed");
+}
+
+/// Run `f` with exclusive access to the touch controller.
+/// Panics if called before `spawn`.
+pub fn with_controller<R>(f: impl FnOnce(&mut Controller) -> R) -> R {
+    let mut guard = CONTROLLER.lock().unwrap();
+    f(guard.as_mut().expect("touch controller not initialised"))
+}
+
+/// Replace the raw → display coordinate transform.
+pub fn set_transform(t: TouchTransform) {
+    *TRANSFORM.lock().unwrap() = t;
+}
+
+pub fn transform() -> TouchTransform {
+    *TRANSFORM.lock().unwrap()
+}
+
+// Only the touch task stores to `TOUCH` and `PRESS_START`.
+fn publish(point: Option<(u16, u16)>) {
+    let packed = TOUCH.load(Ordering::Relaxed);
+    let packed = match point {
+        Some((x, y)) => {
+            if packed >> 32 == 0 {
+                PRESS_START.store(((x as u32) << 16) | y as u32, Ordering::Relaxed);
+            }
+            (1 << 32) | ((x as u64) << 16) | y as u64
+        }
+        None => packed & 0xFFFF_FFFF,
+    };
+    // Release: whoever sees the press also sees its `PRESS_START`.
+    TOUCH.store(packed, Ordering::Release);
+}
+
+/// The current touch state, as the indev callback should report it.
+pub fn state() -> TouchState {
+    let packed = TOUCH.load(Ordering::Acquire);
+    TouchState {
+        pressed: packed >> 32 != 0,
+        x: ((packed >> 16) & 0xFFFF) as i32,
+        y: (packed & 0xFFFF) as i32,
+    }
+}
+
+/// Display coordinates where the current or most recent contact started.
+/// Call it after `state`: the start of the press that reported is visible
+/// by then.
+pub fn press_start() -> (i32, i32) {
+    let packed = PRESS_START.load(Ordering::Relaxed);
+    ((packed >> 16) as i32, (packed & 0xFFFF) as i32)
+}
+
+fn post_gesture(g: Gesture) {
+    log::debug!("gesture: {g:?}");
+    *GESTURE.lock().unwrap() = Some(g);
+}
+
+/// Take the most recent gesture, if one arrived since the last call.
+pub fn take_gesture() -> Option<Gesture> {
+    GESTURE.lock().unwrap().take()
+}
+
+/// `true` if swipes come from the controller's gesture engine, in which case
+/// LVGL's drag-threshold gesture detection should be ignored.
+pub fn hw_gestures_enabled() -> bool {
+    HW_GESTURES.load(Ordering::Relaxed)
+}
+
+/// Time since the last reported contact (or since `spawn` if none yet).
+pub fn idle_time() -> Duration {
+    let Some(epoch) = EPOCH.get() else {
+        return Duration::ZERO;
+    };
+    let now_ms = epoch.elapsed().as_millis() as u32;
+    Duration::from_millis(now_ms.wrapping_sub(LAST_TOUCH_MS.load(Ordering::Relaxed)) as u64)
+}
+
+/// Count as user activity now, as if the screen had been touched: resets
+/// `idle_time`, so auto-dim brings the panel back to full brightness.
+pub fn mark_activity() {
+    if let Some(epoch) = EPOCH.get() {
+        LAST_TOUCH_MS.store(epoch.elapsed().as_millis() as u32, Ordering::Relaxed);
+    }
+}
+
+/// Put the ESP32-S3 into light sleep until the next touch.
+///
+/// The controller is parked in monitor mode with INT in polling mode, so a
+/// touch holds INT low for as long as the finger is down — a level the GPIO
+/// wake-up logic cannot miss, unlike trigger-mode pulses. Afterwards the
+/// controller and the INT edge interrupt are restored, also when a step on
+/// the way failed; the first error is returned.
+pub fn light_sleep_until_touch() -> Result<(), EspError> {
+    let gpio = TP_INT_GPIO.load(Ordering::Relaxed);
+    assert!(gpio >= 0, "touch task not started");
+
+    let parked = with_controller(|ft| {
+        ft.set_interrupt_mode(InterruptMode::Polling)?;
+        ft.set_power_mode(PowerMode::Monitor)
+    })
+    .map_err(to_esp);
+
+    // SAFETY: plain ESP-IDF calls on the already configured TP_INT input.
+    let slept = parked.and_then(|()| unsafe {
+        esp!(sys::gpio_wakeup_enable(gpio, sys::gpio_int_type_t_GPIO_INTR_LOW_LEVEL))?;
+        esp!(sys::esp_sleep_enable_gpio_wakeup())?;
+        log::info!("light sleep until touch");
+        esp!(sys::esp_light_sleep_start())
+    });
+    if slept.is_ok() {
+        mark_activity();
+    }
+
+    // Every step runs whatever happened above.
+    // SAFETY: as above.
+    let gpio_restored = unsafe {
+        let disabled = esp!(sys::gpio_wakeup_disable(gpio));
+        // gpio_wakeup_enable replaced the edge trigger the touch task set up.
+        disabled.and(esp!(sys::gpio_set_intr_type(gpio, sys::gpio_int_type_t_GPIO_INTR_NEGEDGE)))
+    };
+    let controller_restored = with_controller(|ft| {
+        let active = ft.set_power_mode(PowerMode::Active);
+        active.and(ft.set_interrupt_mode(InterruptMode::Trigger))
+    })
+    .map_err(to_esp);
+
+    slept.and(gpio_restored).and(controller_restored)
+}
+
+/// Map a driver error back to the ESP-IDF code it came from.
+fn to_esp(e: ft3168::Error<BusError>) -> EspError {
+    match e {
+        ft3168::Error::Nack(BusError(e))
+        | ft3168::Error::Timeout(BusError(e))
+        | ft3168::Error::Bus(BusError(e)) => e,
+        ft3168::Error::NotResponding => EspError::from(sys::ESP_ERR_TIMEOUT as _).unwrap(),
+        _ => EspError::from(sys::ESP_ERR_INVALID_RESPONSE as _).unwrap(),
+    }
+}
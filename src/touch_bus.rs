@@ -0,0 +1,68 @@
+// src/touch_bus.rs
+// The touch controller's I2C bus, with a bounded timeout. esp-idf-hal's own
+// embedded-hal impl waits with BLOCK, so a wedged bus would hang the touch
+// task forever — with the controller lock held.
+
+use std::time::Duration;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+use esp_idf_svc::hal::delay::TickType;
+use esp_idf_svc::hal::i2c::I2cDriver;
+use esp_idf_svc::sys::{self, EspError, TickType_t};
+
+/// An ESP-IDF I2C error, classified for embedded-hal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusError(pub EspError);
+
+impl BusError {
+    /// For `Ft3168::with_timeout_check`.
+    pub fn is_timeout(&self) -> bool {
+        self.0.code() == sys::ESP_ERR_TIMEOUT as i32
+    }
+}
+
+impl embedded_hal::i2c::Error for BusError {
+    fn kind(&self) -> ErrorKind {
+        // The I2C driver reports a missing ACK as a plain ESP_FAIL.
+        if self.0.code() == sys::ESP_FAIL {
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)
+        } else {
+            ErrorKind::Other
+        }
+    }
+}
+
+/// `I2cDriver` as an embedded-hal bus whose transactions give up after
+/// `timeout`.
+pub struct TouchBus {
+    i2c: I2cDriver<'static>,
+    timeout: TickType_t,
+}
+
+impl TouchBus {
+    pub fn new(i2c: I2cDriver<'static>, timeout: Duration) -> Self {
+        Self { i2c, timeout: TickType::from(timeout).ticks() }
+    }
+}
+
+impl ErrorType for TouchBus {
+    type Error = BusError;
+}
+
+impl I2c for TouchBus {
+    fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), BusError> {
+        self.i2c.transaction(address, operations, self.timeout).map_err(BusError)
+    }
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), BusError> {
+        self.i2c.write(address, bytes, self.timeout).map_err(BusError)
+    }
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), BusError> {
+        self.i2c.read(address, buffer, self.timeout).map_err(BusError)
+    }
+
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), BusError> {
+        self.i2c.write_read(address, bytes, buffer, self.timeout).map_err(BusError)
+    }
+}
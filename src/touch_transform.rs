@@ -0,0 +1,34 @@
+// src/touch_transform.rs
+// Coordinate transform between the FT3168 and LVGL (see
+// `ui_core::touch_transform`), persisted in NVS so a calibration survives
+// reboots.
+
+use std::sync::Mutex;
+
+use esp_idf_svc::nvs::{EspDefaultNvsPartition, EspNvs, NvsDefault};
+use esp_idf_svc::sys::EspError;
+
+pub use ui_core::touch_transform::{Affine, Rotation, TouchTransform, BLOB_LEN};
+
+const NVS_NAMESPACE: &str = "touch";
+const NVS_KEY: &str = "transform";
+
+static NVS: Mutex<Option<EspNvs<NvsDefault>>> = Mutex::new(None);
+
+/// Open the NVS namespace used for the stored transform and return the saved
+/// transform, if there is one.
+pub fn init_storage(partition: EspDefaultNvsPartition) -> Result<Option<TouchTransform>, EspError> {
+    let nvs = EspNvs::new(partition, NVS_NAMESPACE, true)?;
+    let mut buf = [0u8; BLOB_LEN];
+    let stored = nvs.get_raw(NVS_KEY, &mut buf)?.and_then(TouchTransform::from_bytes);
+    *NVS.lock().unwrap() = Some(nvs);
+    Ok(stored)
+}
+
+/// Persist `t` so `init_storage` returns it on the next boot.
+pub fn save(t: &TouchTransform) -> Result<(), EspError> {
+    let mut guard = NVS.lock().unwrap();
+    let nvs = guard.as_mut().expect("touch transform storage not initialised");
+    nvs.set_raw(NVS_KEY, &t.to_bytes())?;
+    Ok(())
+}
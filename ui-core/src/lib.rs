@@ -0,0 +1,11 @@
+// ui-core/src/lib.rs
+//! The parts of the UI that are plain logic: round-display geometry, the
+//! touch coordinate transform and filter, animation stepping and timeline
+//! parsing. No LVGL or ESP-IDF, so everything here builds and is tested on
+//! the host.
+
+pub mod motion;
+pub mod safe_area;
+pub mod timeline;
+pub mod touch_filter;
+pub mod touch_transform;
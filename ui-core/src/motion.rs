@@ -0,0 +1,233 @@
+// ui-core/src/motion.rs
+// The LVGL-free half of sprite animation: easing curves, frame stepping and
+// the values tweens and paths are made of. `anim` in the firmware puts
+// these on screen.
+
+use crate::safe_area;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    /// 0, 1, …, n-1, 0, 1, …
+    Loop,
+    /// 0, 1, …, n-1, n-2, …, 1, 0, 1, …
+    PingPong,
+    /// 0, 1, …, n-1, then stays on the last frame.
+    Once,
+}
+
+/// Easing curves, the same as LVGL's `lv_anim_path_*`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Overshoot,
+}
+
+impl Easing {
+    /// Eased progress for linear progress `t`, both in permille. Same
+    /// control points as LVGL's curves, so a tween and an lv_anim with the
+    /// same easing move alike. `Overshoot` goes past 1000.
+    pub fn apply(self, t: i32) -> i32 {
+        let (u1, u2) = match self {
+            Easing::Linear => return t,
+            Easing::EaseIn => (50, 100),
+            Easing::EaseOut => (900, 950),
+            Easing::EaseInOut => (50, 952),
+            Easing::Overshoot => (1000, 1300),
+        };
+        // Cubic Bézier from 0 to 1024 over t in 0..=1024, as lv_bezier3.
+        let t = t.clamp(0, 1000) as i64 * 1024 / 1000;
+        let r = 1024 - t;
+        let v = (3 * r * r * t * u1 + 3 * r * t * t * u2) / (1 << 30) + t * t * t / (1 << 20);
+        (v * 1000 / 1024) as i32
+    }
+}
+
+/// Per-instance timing on top of the frame durations, so sprites sharing
+/// frames do not animate in lockstep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    /// Start this far into the animation.
+    pub phase_ms: u32,
+    /// Every time a frame is shown it lasts up to this much longer, at
+    /// random.
+    pub jitter_ms: u32,
+}
+
+/// Which frame is showing and for how long. Kept apart from the widget so
+/// the stepping rules do not depend on LVGL.
+#[derive(Debug, Clone)]
+pub struct Playback {
+    durations: Vec<u32>,
+    mode: LoopMode,
+    frame: usize,
+    backwards: bool,
+    elapsed_ms: u32,
+    /// How long the current frame stays: its duration plus jitter.
+    hold_ms: u32,
+    jitter_ms: u32,
+    /// xorshift32 state for the jitter.
+    rng: u32,
+    done: bool,
+}
+
+impl Playback {
+    /// `durations` holds one entry per frame, at least one.
+    pub fn new(durations: Vec<u32>, mode: LoopMode) -> Self {
+        let hold_ms = durations[0];
+        Self {
+            durations,
+            mode,
+            frame: 0,
+            backwards: false,
+            elapsed_ms: 0,
+            hold_ms,
+            jitter_ms: 0,
+            rng: 1,
+            done: false,
+        }
+    }
+
+    /// Play other frames from the start, keeping the jitter.
+    pub fn replace(&mut self, durations: Vec<u32>, mode: LoopMode) {
+        self.durations = durations;
+        self.mode = mode;
+        self.restart();
+    }
+
+    /// Add random jitter, seeded with `seed`, and skip `phase_ms` ahead.
+    pub fn set_timing(&mut self, timing: Timing, seed: u32) {
+        self.jitter_ms = timing.jitter_ms;
+        self.rng = seed.max(1);
+        self.restart();
+        self.advance(timing.phase_ms);
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// `true` once a `Once` playback has reached its last frame.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn hold_for(&mut self, frame: usize) -> u32 {
+        if self.jitter_ms == 0 {
+            return self.durations[frame];
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.durations[frame] + self.rng % (self.jitter_ms + 1)
+    }
+
+    fn next_frame(&mut self) -> Option<usize> {
+        let last = self.durations.len() - 1;
+        match self.mode {
+            LoopMode::Loop => Some(if self.frame == last { 0 } else { self.frame + 1 }),
+            LoopMode::Once if self.frame == last => None,
+            LoopMode::Once => Some(self.frame + 1),
+            LoopMode::PingPong => {
+                if last == 0 {
+                    return Some(0);
+                }
+                if self.frame == last {
+                    self.backwards = true;
+                } else if self.frame == 0 {
+                    self.backwards = false;
+                }
+                Some(if self.backwards { self.frame - 1 } else { self.frame + 1 })
+            }
+        }
+    }
+
+    /// Let `dt_ms` pass. Returns the new frame if it changed.
+    pub fn advance(&mut self, dt_ms: u32) -> Option<usize> {
+        if self.done {
+            return None;
+        }
+        let start = self.frame;
+        self.elapsed_ms += dt_ms;
+        while self.elapsed_ms >= self.hold_ms {
+            self.elapsed_ms -= self.hold_ms;
+            match self.next_frame() {
+                Some(f) => {
+                    self.frame = f;
+                    self.hold_ms = self.hold_for(f);
+                }
+                None => {
+                    self.done = true;
+                    self.elapsed_ms = 0;
+                    break;
+                }
+            }
+        }
+        (self.frame != start).then_some(self.frame)
+    }
+
+    /// Back to the first frame (also un-finishes a `Once` playback).
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.backwards = false;
+        self.elapsed_ms = 0;
+        self.hold_ms = self.hold_for(0);
+        self.done = false;
+    }
+}
+
+/// A sprite property a tween can drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prop {
+    /// Left edge in the parent.
+    X,
+    /// Top edge in the parent.
+    Y,
+    /// 0 (transparent) ..= 255 (opaque).
+    Opa,
+    /// 256 is 1:1, around the sprite's centre.
+    Zoom,
+}
+
+/// Waypoints for a sprite's top-left corner, walked in straight legs at a
+/// steady speed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    points: Vec<(i32, i32)>,
+    speed_px_s: u32,
+    easing: Easing,
+}
+
+impl Path {
+    /// Visit `points` in order at `speed_px_s`, each leg linear.
+    pub fn new(points: &[(i32, i32)], speed_px_s: u32) -> Self {
+        Self { points: points.to_vec(), speed_px_s: speed_px_s.max(1), easing: Easing::Linear }
+    }
+
+    /// Ease every leg, e.g. `EaseInOut` to stop at each waypoint.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn points(&self) -> &[(i32, i32)] {
+        &self.points
+    }
+
+    pub fn speed_px_s(&self) -> u32 {
+        self.speed_px_s
+    }
+
+    /// The easing of every leg.
+    pub fn leg_easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Index of the first waypoint at which a `w`×`h` sprite would not be
+    /// wholly on the round display.
+    pub fn off_display(&self, w: i32, h: i32) -> Option<usize> {
+        self.points.iter().position(|&(x, y)| !safe_area::rect_in_display(x, y, w, h))
+    }
+}
@@ -16,6 +16,58 @@ pub fn point_in_display(x: i32, y: i32) -> bool {
     dx * dx + dy * dy <= DISPLAY_R * DISPLAY_R
 }
 
+/// The point of the display circle nearest to (x, y): (x, y) itself if it
+/// is inside, else the edge point towards it.
+pub fn clamp_point_to_display(x: i32, y: i32) -> (i32, i32) {
+    if point_in_display(x, y) {
+        return (x, y);
+    }
+    let dx = (x - DISPLAY_CX) as f32;
+    let dy = (y - DISPLAY_CY) as f32;
+    let scale = DISPLAY_R as f32 / (dx * dx + dy * dy).sqrt();
+    // Truncating towards the centre keeps the result inside.
+    (DISPLAY_CX + (dx * scale) as i32, DISPLAY_CY + (dy * scale) as i32)
+}
+
+/// Point `inset` px in from the circle's edge at `angle` radians, measured
+/// clockwise from the positive x axis (π/2 is straight down).
+pub fn arc_point(inset: i32, angle: f32) -> (i32, i32) {
+    let r = (DISPLAY_R - inset) as f32;
+    (
+        DISPLAY_CX + (r * angle.cos()).round() as i32,
+        DISPLAY_CY + (r * angle.sin()).round() as i32,
+    )
+}
+
+/// Returns `true` if (x, y) lies in the left bezel: the 90° arc of the
+/// display facing left, no more than `width` px in from the circle's edge.
+/// Points just outside the circle count too.
+pub fn in_left_bezel(x: i32, y: i32, width: i32) -> bool {
+    let dx = x - DISPLAY_CX;
+    let dy = y - DISPLAY_CY;
+    let inner = DISPLAY_R - width;
+    dx < 0 && dy.abs() <= -dx && dx * dx + dy * dy >= inner * inner
+}
+
+/// Visible columns of row `y` as `[x1, x2)`: the pixels of that row for
+/// which `point_in_display` holds. Empty (`x1 == x2`) for rows off the panel.
+pub fn row_span(y: i32) -> (i32, i32) {
+    let dy = y - DISPLAY_CY;
+    let r2 = DISPLAY_R * DISPLAY_R - dy * dy;
+    if r2 < 0 || !(0..DISPLAY_H).contains(&y) {
+        return (DISPLAY_CX, DISPLAY_CX);
+    }
+    let mut half = (r2 as f32).sqrt() as i32;
+    // Correct float rounding so the span matches `point_in_display` exactly.
+    while half * half > r2 {
+        half -= 1;
+    }
+    while (half + 1) * (half + 1) <= r2 {
+        half += 1;
+    }
+    ((DISPLAY_CX - half).max(0), (DISPLAY_CX + half + 1).min(DISPLAY_W))
+}
+
 /// Returns `true` if the entire rectangle [x, x+w) × [y, y+h) lies within
 /// the display circle. All four corners must be inside.
 pub fn rect_in_display(x: i32, y: i32, w: i32, h: i32) -> bool {
@@ -0,0 +1,223 @@
+// ui-core/src/timeline.rs
+// Timeline scripts: timed cues for named actors, parsed from text. The
+// firmware's `timeline::Player` binds a parsed timeline to sprites and runs
+// it; the build parses every scene too, so a broken one fails the build. The
+// format is described in `scenes/bridge.scene`.
+
+use std::fmt;
+
+use crate::motion::{Easing, Path, Prop};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Switch to the named clip.
+    Play(String),
+    /// Tween some properties to these values over `ms`.
+    Tween(Vec<(Prop, i32)>, u32, Easing),
+    Walk(Path),
+    /// Speech bubble with this text for `ms`.
+    Say(String, u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// From the start of the story, or of the reaction.
+    pub at_ms: u32,
+    pub line: usize,
+    /// Part of the reaction to tapping this actor, not of the story.
+    pub tapped: Option<String>,
+    pub actor: String,
+    pub action: Action,
+}
+
+/// A parsed timeline, not yet bound to any sprites.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    /// In time order; events at the same time keep their file order.
+    pub events: Vec<Event>,
+    /// Restart the story after this long; `None` plays it once.
+    pub loop_ms: Option<u32>,
+    /// Actors declared with `cast`, and their clips. Empty if the timeline
+    /// declares none; otherwise every cue sticks to these.
+    pub cast: Vec<(String, Vec<String>)>,
+}
+
+/// What is wrong with a timeline, and on which line (1-based).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.msg)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Timeline {
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        let mut events = Vec::new();
+        let mut loop_ms = None;
+        let mut cast: Vec<(String, Vec<String>)> = Vec::new();
+        for (i, line) in src.lines().enumerate() {
+            let line_no = i + 1;
+            let err = |msg: String| ParseError { line: line_no, msg };
+            let words: Vec<&str> = line.split('#').next().unwrap_or("").split_whitespace().collect();
+            match words.as_slice() {
+                [] => {}
+                ["loop", time] => {
+                    let ms = parse_time(time).map_err(err)?;
+                    if ms == 0 {
+                        return Err(err("loop length must not be zero".into()));
+                    }
+                    loop_ms = Some(ms);
+                }
+                ["cast", actor, clips @ ..] => {
+                    if cast.iter().any(|(name, _)| name == actor) {
+                        return Err(err(format!("`{actor}` is cast twice")));
+                    }
+                    cast.push((actor.to_string(), clips.iter().map(|c| c.to_string()).collect()));
+                }
+                ["cast"] => return Err(err("expected `cast <actor> [clips ...]`".into())),
+                ["tap", tapped, time, actor, action, args @ ..] => events.push(Event {
+                    at_ms: parse_time(time).map_err(err)?,
+                    line: line_no,
+                    tapped: Some(tapped.to_string()),
+                    actor: actor.to_string(),
+                    action: parse_action(action, args).map_err(err)?,
+                }),
+                ["tap", ..] => return Err(err("expected `tap <actor> <time> <actor> <action> ...`".into())),
+                [time, actor, action, args @ ..] => events.push(Event {
+                    at_ms: parse_time(time).map_err(err)?,
+                    line: line_no,
+                    tapped: None,
+                    actor: actor.to_string(),
+                    action: parse_action(action, args).map_err(err)?,
+                }),
+                _ => return Err(err("expected `<time> <actor> <action> ...` or `loop <time>`".into())),
+            }
+        }
+        if let Some(limit) = loop_ms {
+            if let Some(late) = events.iter().find(|e| e.tapped.is_none() && e.at_ms >= limit) {
+                return Err(ParseError { line: late.line, msg: format!("cue at or after the {limit} ms loop") });
+            }
+        }
+        if !cast.is_empty() {
+            for e in &events {
+                check_cast(&cast, e).map_err(|msg| ParseError { line: e.line, msg })?;
+            }
+        }
+        events.sort_by_key(|e| e.at_ms);
+        Ok(Self { events, loop_ms, cast })
+    }
+}
+
+/// `e` names only declared actors, and clips they have.
+fn check_cast(cast: &[(String, Vec<String>)], e: &Event) -> Result<(), String> {
+    let clips = |name: &str| {
+        cast.iter()
+            .find(|(actor, _)| actor == name)
+            .map(|(_, clips)| clips)
+            .ok_or_else(|| format!("`{name}` is not in the cast"))
+    };
+    if let Some(tapped) = &e.tapped {
+        clips(tapped)?;
+    }
+    let actor_clips = clips(&e.actor)?;
+    match &e.action {
+        Action::Play(clip) if !actor_clips.contains(clip) => Err(format!("`{}` has no clip `{clip}`", e.actor)),
+        _ => Ok(()),
+    }
+}
+
+/// `10s`, `1.5s` or `400ms`: plain decimals only, no signs or exponents.
+fn parse_time(s: &str) -> Result<u32, String> {
+    let digits = |d: &str| !d.is_empty() && d.bytes().all(|b| b.is_ascii_digit());
+    let ms = if let Some(ms) = s.strip_suffix("ms").filter(|ms| digits(ms)) {
+        ms.parse::<f64>().ok()
+    } else if let Some(secs) = s.strip_suffix('s') {
+        let (whole, frac) = secs.split_once('.').unwrap_or((secs, "0"));
+        if digits(whole) && digits(frac) {
+            secs.parse::<f64>().ok().map(|s| (s * 1000.0).round())
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    let ms = ms.ok_or_else(|| format!("bad time `{s}`, expected e.g. `10s`, `1.5s` or `400ms`"))?;
+    if ms > u32::MAX as f64 {
+        return Err(format!("time `{s}` is too long, at most {} ms", u32::MAX));
+    }
+    Ok(ms as u32)
+}
+
+fn parse_int(s: &str) -> Result<i32, String> {
+    s.parse().map_err(|_| format!("bad number `{s}`"))
+}
+
+/// `x,y`
+fn parse_point(s: &str) -> Result<(i32, i32), String> {
+    let (x, y) = s.split_once(',').ok_or_else(|| format!("bad point `{s}`, expected `x,y`"))?;
+    Ok((parse_int(x)?, parse_int(y)?))
+}
+
+fn parse_easing(s: &str) -> Result<Easing, String> {
+    Ok(match s {
+        "linear" => Easing::Linear,
+        "ease-in" => Easing::EaseIn,
+        "ease-out" => Easing::EaseOut,
+        "ease-in-out" => Easing::EaseInOut,
+        "overshoot" => Easing::Overshoot,
+        _ => return Err(format!("unknown easing `{s}`")),
+    })
+}
+
+/// `<time> [easing]` after a tween's target.
+fn parse_tween_timing(args: &[&str]) -> Result<(u32, Easing), String> {
+    match args {
+        [time] => Ok((parse_time(time)?, Easing::Linear)),
+        [time, easing] => Ok((parse_time(time)?, parse_easing(easing)?)),
+        _ => Err("expected `<time> [easing]` after the target".into()),
+    }
+}
+
+fn parse_action(action: &str, args: &[&str]) -> Result<Action, String> {
+    match (action, args) {
+        ("play", [clip]) => Ok(Action::Play(clip.to_string())),
+        ("move", [point, rest @ ..]) => {
+            let (x, y) = parse_point(point)?;
+            let (ms, easing) = parse_tween_timing(rest)?;
+            Ok(Action::Tween(vec![(Prop::X, x), (Prop::Y, y)], ms, easing))
+        }
+        ("fade", [opa, rest @ ..]) => {
+            let (ms, easing) = parse_tween_timing(rest)?;
+            Ok(Action::Tween(vec![(Prop::Opa, parse_int(opa)?)], ms, easing))
+        }
+        ("zoom", [zoom, rest @ ..]) => {
+            let (ms, easing) = parse_tween_timing(rest)?;
+            Ok(Action::Tween(vec![(Prop::Zoom, parse_int(zoom)?)], ms, easing))
+        }
+        ("walk", [speed, rest @ ..]) => {
+            let speed = parse_int(speed)?;
+            if speed <= 0 {
+                return Err("walking speed must be positive".into());
+            }
+            let (easing, points) = match rest {
+                [first, points @ ..] if !first.contains(',') => (parse_easing(first)?, points),
+                _ => (Easing::Linear, rest),
+            };
+            if points.is_empty() {
+                return Err("walk needs at least one waypoint".into());
+            }
+            let points = points.iter().map(|p| parse_point(p)).collect::<Result<Vec<_>, _>>()?;
+            Ok(Action::Walk(Path::new(&points, speed as u32).easing(easing)))
+        }
+        ("say", [time, words @ ..]) if !words.is_empty() => Ok(Action::Say(words.join(" "), parse_time(time)?)),
+        ("play" | "move" | "fade" | "zoom" | "walk" | "say", _) => Err(format!("wrong arguments for `{action}`")),
+        _ => Err(format!("unknown action `{action}`")),
+    }
+}
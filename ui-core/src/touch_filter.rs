@@ -0,0 +1,132 @@
+// ui-core/src/touch_filter.rs
+// Filter stage between the touch transform and the published touch state:
+// dead-zone rejection outside the visible circle, press/release debounce,
+// then a 3-sample median followed by IIR smoothing.
+
+use crate::safe_area;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterConfig {
+    /// Consecutive contact samples required before a press is reported.
+    pub press_samples: u8,
+    /// Consecutive empty samples required before a release is reported.
+    pub release_samples: u8,
+    /// Run a 3-sample median per axis to drop single-sample spikes.
+    pub median: bool,
+    /// IIR weight of the newest sample, 0 < alpha <= 1 (1 disables smoothing).
+    pub iir_alpha: f32,
+    /// Ignore presses that start outside the visible circle. A contact that
+    /// slides out is kept, pulled onto the edge, so edge swipes survive.
+    pub reject_outside_circle: bool,
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        FilterConfig {
+            press_samples: 2,
+            release_samples: 2,
+            median: true,
+            iir_alpha: 0.5,
+            reject_outside_circle: true,
+        }
+    }
+}
+
+pub struct TouchFilter {
+    cfg: FilterConfig,
+    pressed: bool,
+    // Consecutive samples disagreeing with `pressed`.
+    pending: u8,
+    history: [(f32, f32); 3],
+    history_len: usize,
+    smoothed: (f32, f32),
+}
+
+impl TouchFilter {
+    pub fn new(cfg: FilterConfig) -> Self {
+        Self {
+            cfg,
+            pressed: false,
+            pending: 0,
+            history: [(0.0, 0.0); 3],
+            history_len: 0,
+            smoothed: (0.0, 0.0),
+        }
+    }
+
+    /// `true` while a press is reported or one is being debounced in or out,
+    /// i.e. while the caller should keep feeding samples.
+    pub fn is_active(&self) -> bool {
+        self.pressed || self.pending > 0
+    }
+
+    /// Feed one sample (`None` = no contact) and get the filtered state.
+    pub fn update(&mut self, raw: Option<(u16, u16)>) -> Option<(u16, u16)> {
+        let raw = match raw {
+            Some((x, y))
+                if self.cfg.reject_outside_circle
+                    && !safe_area::point_in_display(x as i32, y as i32) =>
+            {
+                // Only a contact that is already reported may leave the circle.
+                self.pressed.then(|| {
+                    let (x, y) = safe_area::clamp_point_to_display(x as i32, y as i32);
+                    (x as u16, y as u16)
+                })
+            }
+            raw => raw,
+        };
+
+        match (self.pressed, raw) {
+            (false, None) => self.pending = 0,
+            (false, Some(p)) => {
+                self.pending += 1;
+                if self.pending >= self.cfg.press_samples {
+                    self.pressed = true;
+                    self.pending = 0;
+                    self.history_len = 0;
+                    self.smoothed = (p.0 as f32, p.1 as f32);
+                }
+            }
+            (true, None) => {
+                self.pending += 1;
+                if self.pending >= self.cfg.release_samples {
+                    self.pressed = false;
+                    self.pending = 0;
+                }
+            }
+            (true, Some(_)) => self.pending = 0,
+        }
+
+        if !self.pressed {
+            return None;
+        }
+        if let Some((x, y)) = raw {
+            self.smooth(x as f32, y as f32);
+        }
+        // While a release is being debounced, hold the last filtered point.
+        Some((self.smoothed.0.round() as u16, self.smoothed.1.round() as u16))
+    }
+
+    fn smooth(&mut self, x: f32, y: f32) {
+        let (mx, my) = if self.cfg.median {
+            self.history.rotate_right(1);
+            self.history[0] = (x, y);
+            self.history_len = (self.history_len + 1).min(3);
+            if self.history_len < 3 {
+                (x, y)
+            } else {
+                let [a, b, c] = self.history;
+                (median3(a.0, b.0, c.0), median3(a.1, b.1, c.1))
+            }
+        } else {
+            (x, y)
+        };
+        let alpha = self.cfg.iir_alpha.clamp(0.01, 1.0);
+        self.smoothed.0 += alpha * (mx - self.smoothed.0);
+        self.smoothed.1 += alpha * (my - self.smoothed.1);
+    }
+}
+
+fn median3(a: f32, b: f32, c: f32) -> f32 {
+    a.max(b).min(a.min(b).max(c))
+}
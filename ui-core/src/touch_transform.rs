@@ -0,0 +1,168 @@
+// ui-core/src/touch_transform.rs
+// Coordinate transform between the FT3168 and LVGL: panel gap, mirroring,
+// rotation, then an affine calibration matrix, plus the blob it is
+// persisted as.
+
+use crate::safe_area::{DISPLAY_H, DISPLAY_W};
+
+const BLOB_VERSION: u8 = 1;
+/// Length of `TouchTransform::to_bytes`.
+pub const BLOB_LEN: usize = 32;
+
+/// Panel rotation, clockwise, applied to touch coordinates so they follow
+/// the rotated display content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0 => Some(Rotation::Deg0),
+            1 => Some(Rotation::Deg90),
+            2 => Some(Rotation::Deg180),
+            3 => Some(Rotation::Deg270),
+            _ => None,
+        }
+    }
+}
+
+/// x' = a·x + b·y + c,  y' = d·x + e·y + f
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Affine {
+    pub const IDENTITY: Affine = Affine { a: 1.0, b: 0.0, c: 0.0, d: 0.0, e: 1.0, f: 0.0 };
+
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.b * y + self.c,
+            self.d * x + self.e * y + self.f,
+        )
+    }
+
+    /// Solve the affine map taking each `measured` point onto its `target`.
+    /// Returns `None` if the three points are (nearly) collinear.
+    pub fn from_points(measured: [(f32, f32); 3], target: [(f32, f32); 3]) -> Option<Affine> {
+        let [(x0, y0), (x1, y1), (x2, y2)] = measured;
+        // Cramer's rule on | xi yi 1 | · (a b c)ᵀ = Xi, same matrix for (d e f).
+        let det = x0 * (y1 - y2) - y0 * (x1 - x2) + (x1 * y2 - x2 * y1);
+        if det.abs() < 1.0 {
+            return None;
+        }
+        let solve = |t0: f32, t1: f32, t2: f32| {
+            let p = (t0 * (y1 - y2) - y0 * (t1 - t2) + (t1 * y2 - t2 * y1)) / det;
+            let q = (x0 * (t1 - t2) - t0 * (x1 - x2) + (x1 * t2 - x2 * t1)) / det;
+            let r = (x0 * (y1 * t2 - y2 * t1) - y0 * (x1 * t2 - x2 * t1) + t0 * (x1 * y2 - x2 * y1)) / det;
+            (p, q, r)
+        };
+        let (a, b, c) = solve(target[0].0, target[1].0, target[2].0);
+        let (d, e, f) = solve(target[0].1, target[1].1, target[2].1);
+        Some(Affine { a, b, c, d, e, f })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchTransform {
+    /// Offset subtracted from raw coordinates first, e.g. the CO5300's 6 px
+    /// x-gap when the digitizer is aligned to the panel RAM rather than the glass.
+    pub gap: (i16, i16),
+    pub mirror_x: bool,
+    pub mirror_y: bool,
+    pub rotation: Rotation,
+    pub calibration: Affine,
+}
+
+impl TouchTransform {
+    pub const IDENTITY: TouchTransform = TouchTransform {
+        gap: (0, 0),
+        mirror_x: false,
+        mirror_y: false,
+        rotation: Rotation::Deg0,
+        calibration: Affine::IDENTITY,
+    };
+
+    /// Subtract the panel's RAM gap from raw coordinates.
+    pub fn with_gap(mut self, x: i16, y: i16) -> Self {
+        self.gap = (x, y);
+        self
+    }
+
+    /// Mirror, then rotate, so touches follow the displayed content.
+    pub fn with_orientation(mut self, rotation: Rotation, mirror_x: bool, mirror_y: bool) -> Self {
+        self.rotation = rotation;
+        self.mirror_x = mirror_x;
+        self.mirror_y = mirror_y;
+        self
+    }
+
+    /// Map a raw controller point to display coordinates, clamped to the panel.
+    pub fn apply(&self, x: u16, y: u16) -> (u16, u16) {
+        let max_x = DISPLAY_W - 1;
+        let max_y = DISPLAY_H - 1;
+
+        let mut x = x as i32 - self.gap.0 as i32;
+        let mut y = y as i32 - self.gap.1 as i32;
+        if self.mirror_x {
+            x = max_x - x;
+        }
+        if self.mirror_y {
+            y = max_y - y;
+        }
+        let (x, y) = match self.rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (max_x - y, x),
+            Rotation::Deg180 => (max_x - x, max_y - y),
+            Rotation::Deg270 => (y, max_y - x),
+        };
+        let (x, y) = self.calibration.apply(x as f32, y as f32);
+        (
+            (x.round() as i32).clamp(0, max_x) as u16,
+            (y.round() as i32).clamp(0, max_y) as u16,
+        )
+    }
+
+    /// Serialised form, as stored in NVS.
+    pub fn to_bytes(self) -> [u8; BLOB_LEN] {
+        let mut b = [0u8; BLOB_LEN];
+        b[0] = BLOB_VERSION;
+        b[1] = self.rotation as u8;
+        b[2] = self.mirror_x as u8 | (self.mirror_y as u8) << 1;
+        b[4..6].copy_from_slice(&self.gap.0.to_le_bytes());
+        b[6..8].copy_from_slice(&self.gap.1.to_le_bytes());
+        let m = self.calibration;
+        for (i, v) in [m.a, m.b, m.c, m.d, m.e, m.f].into_iter().enumerate() {
+            b[8 + i * 4..12 + i * 4].copy_from_slice(&v.to_le_bytes());
+        }
+        b
+    }
+
+    /// Inverse of `to_bytes`; `None` for a blob of another length or version.
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        if b.len() != BLOB_LEN || b[0] != BLOB_VERSION {
+            return None;
+        }
+        let f = |i: usize| f32::from_le_bytes(b[8 + i * 4..12 + i * 4].try_into().unwrap());
+        Some(TouchTransform {
+            gap: (
+                i16::from_le_bytes([b[4], b[5]]),
+                i16::from_le_bytes([b[6], b[7]]),
+            ),
+            mirror_x: b[2] & 0x01 != 0,
+            mirror_y: b[2] & 0x02 != 0,
+            rotation: Rotation::from_u8(b[1])?,
+            calibration: Affine { a: f(0), b: f(1), c: f(2), d: f(3), e: f(4), f: f(5) },
+        })
+    }
+}
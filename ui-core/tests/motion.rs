@@ -0,0 +1,137 @@
+// ui-core/tests/motion.rs
+use ui_core::motion::{Easing, LoopMode, Path, Playback, Timing};
+
+const EASINGS: [Easing; 5] = [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::Overshoot];
+
+/// Frames shown while `dt_ms` passes `steps` times, starting with the first.
+fn frames(p: &mut Playback, dt_ms: u32, steps: usize) -> Vec<usize> {
+    let mut out = vec![p.frame()];
+    for _ in 0..steps {
+        p.advance(dt_ms);
+        out.push(p.frame());
+    }
+    out
+}
+
+#[test]
+fn easings_start_at_zero_and_end_at_one() {
+    for e in EASINGS {
+        assert_eq!(e.apply(0), 0, "{e:?}");
+        assert!((e.apply(1000) - 1000).abs() <= 1, "{e:?}: {}", e.apply(1000));
+    }
+}
+
+#[test]
+fn easings_clamp_progress() {
+    for e in EASINGS.into_iter().skip(1) {
+        assert_eq!(e.apply(-50), e.apply(0), "{e:?}");
+        assert_eq!(e.apply(1500), e.apply(1000), "{e:?}");
+    }
+}
+
+#[test]
+fn easing_shapes() {
+    assert_eq!(Easing::Linear.apply(300), 300);
+    assert!(Easing::EaseIn.apply(300) < 300);
+    assert!(Easing::EaseOut.apply(300) > 300);
+    assert!(Easing::EaseInOut.apply(200) < 200 && Easing::EaseInOut.apply(800) > 800);
+    assert!((0..=1000).step_by(10).any(|t| Easing::Overshoot.apply(t) > 1000));
+}
+
+#[test]
+fn easings_other_than_overshoot_never_go_back() {
+    for e in &EASINGS[..4] {
+        let values: Vec<i32> = (0..=1000).step_by(5).map(|t| e.apply(t)).collect();
+        assert!(values.windows(2).all(|w| w[0] <= w[1]), "{e:?}");
+    }
+}
+
+#[test]
+fn loop_wraps_around() {
+    let mut p = Playback::new(vec![100; 3], LoopMode::Loop);
+    assert_eq!(frames(&mut p, 100, 4), [0, 1, 2, 0, 1]);
+}
+
+#[test]
+fn ping_pong_turns_at_both_ends() {
+    let mut p = Playback::new(vec![100; 3], LoopMode::PingPong);
+    assert_eq!(frames(&mut p, 100, 6), [0, 1, 2, 1, 0, 1, 2]);
+}
+
+#[test]
+fn once_stops_on_the_last_frame() {
+    let mut p = Playback::new(vec![100; 3], LoopMode::Once);
+    assert_eq!(frames(&mut p, 100, 4), [0, 1, 2, 2, 2]);
+    assert!(p.is_done());
+    assert_eq!(p.advance(1000), None);
+    p.restart();
+    assert_eq!((p.frame(), p.is_done()), (0, false));
+}
+
+#[test]
+fn late_time_carries_over() {
+    let mut p = Playback::new(vec![100, 50, 100], LoopMode::Loop);
+    // 160 ms: through frames 0 (100) and 1 (50), 10 ms into frame 2.
+    assert_eq!(p.advance(160), Some(2));
+    assert_eq!(p.advance(80), None);
+    assert_eq!(p.advance(10), Some(0));
+}
+
+#[test]
+fn advance_reports_only_changes() {
+    let mut p = Playback::new(vec![100; 2], LoopMode::Loop);
+    assert_eq!(p.advance(40), None);
+    assert_eq!(p.advance(60), Some(1));
+    // A whole cycle lands on the same frame.
+    assert_eq!(p.advance(200), None);
+}
+
+#[test]
+fn phase_skips_ahead() {
+    let mut p = Playback::new(vec![100; 4], LoopMode::Loop);
+    p.set_timing(Timing { phase_ms: 250, jitter_ms: 0 }, 7);
+    assert_eq!(p.frame(), 2);
+    assert_eq!(p.advance(50), Some(3));
+}
+
+#[test]
+fn jitter_only_lengthens_frames() {
+    let mut p = Playback::new(vec![100; 2], LoopMode::Loop);
+    p.set_timing(Timing { phase_ms: 0, jitter_ms: 30 }, 12345);
+    let mut changes = 0;
+    let mut held = 0;
+    for _ in 0..2000 {
+        held += 1;
+        if p.advance(1).is_some() {
+            assert!((100..=130).contains(&held), "frame held {held} ms");
+            held = 0;
+            changes += 1;
+        }
+    }
+    assert!(changes > 10);
+}
+
+#[test]
+fn replace_restarts_with_new_frames() {
+    let mut p = Playback::new(vec![100; 3], LoopMode::Loop);
+    p.advance(150);
+    p.replace(vec![10], LoopMode::Once);
+    assert_eq!(p.frame(), 0);
+    p.advance(10);
+    assert!(p.is_done());
+}
+
+#[test]
+fn path_keeps_a_positive_speed() {
+    let path = Path::new(&[(10, 20), (30, 40)], 0).easing(Easing::EaseOut);
+    assert_eq!(path.speed_px_s(), 1);
+    assert_eq!(path.points(), [(10, 20), (30, 40)]);
+    assert_eq!(path.leg_easing(), Easing::EaseOut);
+}
+
+#[test]
+fn path_finds_waypoints_off_the_display() {
+    let path = Path::new(&[(200, 200), (2, 2), (400, 200)], 60);
+    assert_eq!(path.off_display(40, 40), Some(1));
+    assert_eq!(Path::new(&[(200, 200)], 60).off_display(40, 40), None);
+}
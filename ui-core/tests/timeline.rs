@@ -0,0 +1,118 @@
+// ui-core/tests/timeline.rs
+use ui_core::motion::{Easing, Path, Prop};
+use ui_core::timeline::{Action, ParseError, Timeline};
+
+fn parse_err(src: &str) -> ParseError {
+    Timeline::parse(src).expect_err("parsed")
+}
+
+#[test]
+fn shipped_scenes_parse() {
+    let t = Timeline::parse(include_str!("../../scenes/bridge.scene")).unwrap();
+    assert_eq!(t.loop_ms, Some(40_000));
+    assert!(!t.cast.is_empty());
+    assert!(t.events.iter().any(|e| e.tapped.is_some()));
+}
+
+#[test]
+fn cues_and_comments() {
+    let t = Timeline::parse(
+        "# a comment\n\
+         \n\
+         1.5s  crew1  play turn   # trailing comment\n\
+         400ms crew1  move 10,20 1s ease-out\n\
+         0s    crew1  fade 128 250ms\n\
+         2s    crew1  zoom 512 1s overshoot\n\
+         3s    crew1  walk 60 ease-in-out 10,10 20,20\n\
+         4s    crew1  say 2s Hello   there\n",
+    )
+    .unwrap();
+    let actions: Vec<(u32, &Action)> = t.events.iter().map(|e| (e.at_ms, &e.action)).collect();
+    assert_eq!(
+        actions,
+        [
+            (0, &Action::Tween(vec![(Prop::Opa, 128)], 250, Easing::Linear)),
+            (400, &Action::Tween(vec![(Prop::X, 10), (Prop::Y, 20)], 1000, Easing::EaseOut)),
+            (1500, &Action::Play("turn".into())),
+            (2000, &Action::Tween(vec![(Prop::Zoom, 512)], 1000, Easing::Overshoot)),
+            (3000, &Action::Walk(Path::new(&[(10, 10), (20, 20)], 60).easing(Easing::EaseInOut))),
+            (4000, &Action::Say("Hello there".into(), 2000)),
+        ]
+    );
+    assert_eq!(t.loop_ms, None);
+    assert_eq!(t.events[2].line, 3);
+}
+
+#[test]
+fn events_at_the_same_time_keep_file_order() {
+    let t = Timeline::parse("1s a play x\n0s b play y\n1s c play z\n").unwrap();
+    let order: Vec<&str> = t.events.iter().map(|e| e.actor.as_str()).collect();
+    assert_eq!(order, ["b", "a", "c"]);
+}
+
+#[test]
+fn reactions() {
+    let t = Timeline::parse("tap crew1 0.5s blink play alert\n").unwrap();
+    let e = &t.events[0];
+    assert_eq!((e.tapped.as_deref(), e.at_ms, e.actor.as_str()), (Some("crew1"), 500, "blink"));
+    assert_eq!(parse_err("tap crew1 0s blink\n").line, 1);
+}
+
+#[test]
+fn loop_bounds_the_story_but_not_reactions() {
+    assert!(Timeline::parse("loop 10s\n9.9s a play x\ntap a 20s a play x\n").is_ok());
+    let e = parse_err("loop 10s\n\n10s a play x\n");
+    assert_eq!(e.line, 3);
+    assert_eq!(parse_err("loop 0s\n").msg, "loop length must not be zero");
+}
+
+#[test]
+fn errors_name_the_line() {
+    let cases = [
+        ("1s a jump\n", "unknown action `jump`"),
+        ("soon a play x\n", "bad time `soon`, expected e.g. `10s`, `1.5s` or `400ms`"),
+        ("-1s a play x\n", "bad time `-1s`, expected e.g. `10s`, `1.5s` or `400ms`"),
+        ("1e3s a play x\n", "bad time `1e3s`, expected e.g. `10s`, `1.5s` or `400ms`"),
+        ("+5ms a play x\n", "bad time `+5ms`, expected e.g. `10s`, `1.5s` or `400ms`"),
+        (".5s a play x\n", "bad time `.5s`, expected e.g. `10s`, `1.5s` or `400ms`"),
+        ("5000000s a play x\n", "time `5000000s` is too long, at most 4294967295 ms"),
+        ("5000000000ms a play x\n", "time `5000000000ms` is too long, at most 4294967295 ms"),
+        ("1s a move 10 1s\n", "bad point `10`, expected `x,y`"),
+        ("1s a fade 10 1s bouncy\n", "unknown easing `bouncy`"),
+        ("1s a walk 0 1,1\n", "walking speed must be positive"),
+        ("1s a walk 60\n", "walk needs at least one waypoint"),
+        ("1s a say 2s\n", "wrong arguments for `say`"),
+        ("1s a\n", "expected `<time> <actor> <action> ...` or `loop <time>`"),
+    ];
+    for (src, msg) in cases {
+        let src = format!("# header\n{src}");
+        assert_eq!(parse_err(&src), ParseError { line: 2, msg: msg.into() }, "{src}");
+    }
+}
+
+#[test]
+fn cast_checks_actors_and_clips() {
+    let cast = "cast crew1 idle turn\ncast blink idle alert\n";
+    assert!(Timeline::parse(&format!("{cast}1s crew1 play turn\ntap crew1 0s blink play alert\n")).is_ok());
+
+    let e = parse_err(&format!("{cast}1s crew4 play turn\n"));
+    assert_eq!((e.line, e.msg.as_str()), (3, "`crew4` is not in the cast"));
+    let e = parse_err(&format!("{cast}tap crew9 0s blink play alert\n"));
+    assert_eq!(e.msg, "`crew9` is not in the cast");
+    let e = parse_err(&format!("{cast}1s blink play turn\n"));
+    assert_eq!(e.msg, "`blink` has no clip `turn`");
+    // Only `play` names a clip.
+    assert!(Timeline::parse(&format!("{cast}1s blink fade 0 1s\n")).is_ok());
+}
+
+#[test]
+fn cast_is_declared_once_per_actor() {
+    assert_eq!(parse_err("cast a x\ncast a y\n").line, 2);
+    assert_eq!(parse_err("cast\n").line, 1);
+}
+
+#[test]
+fn without_a_cast_any_names_parse() {
+    let t = Timeline::parse("1s anyone play anything\n").unwrap();
+    assert!(t.cast.is_empty());
+}
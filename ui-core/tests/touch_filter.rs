@@ -0,0 +1,95 @@
+// ui-core/tests/touch_filter.rs
+use ui_core::safe_area::{self, DISPLAY_CX, DISPLAY_CY};
+use ui_core::touch_filter::{FilterConfig, TouchFilter};
+
+/// Debounce only: no median, no smoothing.
+fn raw_filter() -> TouchFilter {
+    TouchFilter::new(FilterConfig { median: false, iir_alpha: 1.0, ..FilterConfig::default() })
+}
+
+const CENTRE: (u16, u16) = (DISPLAY_CX as u16, DISPLAY_CY as u16);
+
+#[test]
+fn press_and_release_are_debounced() {
+    let mut f = raw_filter();
+    assert_eq!(f.update(Some(CENTRE)), None);
+    assert!(f.is_active());
+    assert_eq!(f.update(Some(CENTRE)), Some(CENTRE));
+    // One empty sample holds the point, the second releases.
+    assert_eq!(f.update(None), Some(CENTRE));
+    assert_eq!(f.update(None), None);
+    assert!(!f.is_active());
+}
+
+#[test]
+fn single_sample_blip_never_presses() {
+    let mut f = raw_filter();
+    assert_eq!(f.update(Some(CENTRE)), None);
+    assert_eq!(f.update(None), None);
+    assert_eq!(f.update(Some(CENTRE)), None);
+    assert!(f.is_active());
+}
+
+#[test]
+fn press_outside_the_circle_is_rejected() {
+    let mut f = raw_filter();
+    for _ in 0..4 {
+        assert_eq!(f.update(Some((3, 3))), None);
+    }
+    assert!(!f.is_active());
+}
+
+#[test]
+fn contact_sliding_off_the_edge_stays_pressed_on_the_edge() {
+    let mut f = raw_filter();
+    f.update(Some((40, 233)));
+    assert_eq!(f.update(Some((40, 233))), Some((40, 233)));
+    // Swiping left past the bezel: still pressed, pulled onto the circle.
+    for x in [20, 5, 0] {
+        let (px, py) = f.update(Some((x, 233))).expect("released mid-drag");
+        assert!(safe_area::point_in_display(px as i32, py as i32));
+        assert_eq!(py, 233);
+    }
+    let corner = f.update(Some((0, 0))).expect("released mid-drag");
+    assert!(safe_area::point_in_display(corner.0 as i32, corner.1 as i32));
+}
+
+#[test]
+fn rejection_can_be_disabled() {
+    let mut f = TouchFilter::new(FilterConfig {
+        median: false,
+        iir_alpha: 1.0,
+        reject_outside_circle: false,
+        ..FilterConfig::default()
+    });
+    f.update(Some((3, 3)));
+    assert_eq!(f.update(Some((3, 3))), Some((3, 3)));
+}
+
+#[test]
+fn median_drops_a_single_spike() {
+    let mut f = TouchFilter::new(FilterConfig { iir_alpha: 1.0, ..FilterConfig::default() });
+    f.update(Some((200, 200)));
+    f.update(Some((200, 200)));
+    f.update(Some((202, 201)));
+    assert_eq!(f.update(Some((300, 300))), Some((202, 201)));
+    assert_eq!(f.update(Some((204, 202))), Some((204, 202)));
+}
+
+#[test]
+fn smoothing_moves_part_of_the_way() {
+    let mut f = TouchFilter::new(FilterConfig { median: false, iir_alpha: 0.5, ..FilterConfig::default() });
+    f.update(Some((200, 200)));
+    f.update(Some((200, 200)));
+    assert_eq!(f.update(Some((220, 180))), Some((210, 190)));
+}
+
+#[test]
+fn clamp_point_to_display_keeps_inside_points() {
+    assert_eq!(safe_area::clamp_point_to_display(100, 300), (100, 300));
+    let (x, y) = safe_area::clamp_point_to_display(-50, DISPLAY_CY);
+    assert_eq!((x, y), (0, DISPLAY_CY));
+    let (x, y) = safe_area::clamp_point_to_display(465, 465);
+    assert!(safe_area::point_in_display(x, y));
+    assert!(x > DISPLAY_CX && y > DISPLAY_CY);
+}
@@ -0,0 +1,87 @@
+// ui-core/tests/touch_transform.rs
+use ui_core::touch_transform::{Affine, Rotation, TouchTransform};
+
+fn close(a: (f32, f32), b: (f32, f32)) -> bool {
+    (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3
+}
+
+#[test]
+fn identity_passes_points_through() {
+    let t = TouchTransform::IDENTITY;
+    assert_eq!(t.apply(0, 0), (0, 0));
+    assert_eq!(t.apply(120, 340), (120, 340));
+    assert_eq!(t.apply(465, 465), (465, 465));
+}
+
+#[test]
+fn gap_is_subtracted_and_clamped() {
+    let t = TouchTransform::IDENTITY.with_gap(6, 0);
+    assert_eq!(t.apply(106, 50), (100, 50));
+    // Raw points inside the gap land on the panel edge.
+    assert_eq!(t.apply(3, 50), (0, 50));
+}
+
+#[test]
+fn mirroring_flips_each_axis() {
+    let t = TouchTransform::IDENTITY.with_orientation(Rotation::Deg0, true, false);
+    assert_eq!(t.apply(10, 20), (455, 20));
+    let t = TouchTransform::IDENTITY.with_orientation(Rotation::Deg0, false, true);
+    assert_eq!(t.apply(10, 20), (10, 445));
+}
+
+#[test]
+fn rotation_turns_clockwise() {
+    let rotated = |r| TouchTransform::IDENTITY.with_orientation(r, false, false).apply(10, 20);
+    assert_eq!(rotated(Rotation::Deg90), (445, 10));
+    assert_eq!(rotated(Rotation::Deg180), (455, 445));
+    assert_eq!(rotated(Rotation::Deg270), (20, 455));
+}
+
+#[test]
+fn calibration_is_applied_last_and_clamped() {
+    let calibration = Affine { a: 1.0, b: 0.0, c: 10.0, d: 0.0, e: 1.0, f: -5.0 };
+    let t = TouchTransform { calibration, ..TouchTransform::IDENTITY.with_gap(6, 0) };
+    assert_eq!(t.apply(106, 50), (110, 45));
+    assert_eq!(t.apply(465, 2), (465, 0));
+}
+
+#[test]
+fn affine_apply() {
+    let m = Affine { a: 2.0, b: 0.5, c: 10.0, d: -1.0, e: 1.0, f: 3.0 };
+    assert!(close(m.apply(4.0, 8.0), (22.0, 7.0)));
+    assert!(close(Affine::IDENTITY.apply(4.0, 8.0), (4.0, 8.0)));
+}
+
+#[test]
+fn from_points_recovers_the_map() {
+    let m = Affine { a: 0.98, b: 0.03, c: -4.0, d: -0.02, e: 1.05, f: 7.5 };
+    let measured = [(233.0, 90.0), (110.0, 330.0), (356.0, 330.0)];
+    let target = measured.map(|(x, y)| m.apply(x, y));
+    let solved = Affine::from_points(measured, target).unwrap();
+    for (p, t) in measured.iter().zip(target) {
+        assert!(close(solved.apply(p.0, p.1), t));
+    }
+    // Off the three samples too.
+    assert!(close(solved.apply(20.0, 400.0), m.apply(20.0, 400.0)));
+}
+
+#[test]
+fn from_points_rejects_collinear_samples() {
+    let measured = [(10.0, 10.0), (100.0, 100.0), (200.0, 200.0)];
+    let target = [(233.0, 90.0), (110.0, 330.0), (356.0, 330.0)];
+    assert_eq!(Affine::from_points(measured, target), None);
+}
+
+#[test]
+fn blob_round_trip() {
+    let t = TouchTransform {
+        calibration: Affine { a: 0.98, b: 0.03, c: -4.0, d: -0.02, e: 1.05, f: 7.5 },
+        ..TouchTransform::IDENTITY.with_gap(6, -2).with_orientation(Rotation::Deg270, true, false)
+    };
+    let bytes = t.to_bytes();
+    assert_eq!(TouchTransform::from_bytes(&bytes), Some(t));
+    assert_eq!(TouchTransform::from_bytes(&bytes[..20]), None);
+    let mut unknown = bytes;
+    unknown[0] = 0xFF;
+    assert_eq!(TouchTransform::from_bytes(&unknown), None);
+}